@@ -0,0 +1,160 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub count: usize,
+    pub bytes: usize,
+    pub peak: usize,
+}
+
+// Measurements should not be nested, as each one resets the peak.
+#[inline]
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, Option<Allocations>) {
+    let measurement = Measurement::start();
+    let result = f();
+    (result, measurement.finish())
+}
+
+#[must_use]
+pub struct Measurement {
+    #[cfg(feature = "count-allocations")]
+    before: counting::Counter,
+}
+
+impl Measurement {
+    #[inline]
+    pub fn start() -> Self {
+        Self {
+            #[cfg(feature = "count-allocations")]
+            before: counting::start(),
+        }
+    }
+
+    #[inline]
+    #[allow(clippy::unused_self)]
+    pub fn finish(self) -> Option<Allocations> {
+        #[cfg(feature = "count-allocations")]
+        {
+            Some(counting::since(self.before))
+        }
+        #[cfg(not(feature = "count-allocations"))]
+        {
+            None
+        }
+    }
+}
+
+#[cfg(feature = "count-allocations")]
+mod counting {
+    use super::Allocations;
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    struct Counting;
+
+    #[derive(Clone, Copy)]
+    pub(super) struct Counter {
+        count: usize,
+        bytes: usize,
+        // Signed, as memory can be freed on another thread than it was allocated on.
+        live: isize,
+        peak: isize,
+    }
+
+    thread_local! {
+        static COUNTER: Cell<Counter> = const {
+            Cell::new(Counter {
+                count: 0,
+                bytes: 0,
+                live: 0,
+                peak: 0,
+            })
+        };
+    }
+
+    fn record(freed: usize, allocated: usize) {
+        // The counter may already be gone while the thread shuts down.
+        let _ = COUNTER.try_with(|counter| {
+            let mut c = counter.get();
+            if allocated > 0 {
+                c.count += 1;
+                c.bytes += allocated;
+            }
+            c.live += allocated as isize - freed as isize;
+            c.peak = c.peak.max(c.live);
+            counter.set(c);
+        });
+    }
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(0, layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record(0, layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record(layout.size(), 0);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record(layout.size(), new_size);
+            }
+            new_ptr
+        }
+    }
+
+    pub(super) fn start() -> Counter {
+        COUNTER.with(|counter| {
+            let mut c = counter.get();
+            c.peak = c.live;
+            counter.set(c);
+            c
+        })
+    }
+
+    pub(super) fn since(before: Counter) -> Allocations {
+        let after = COUNTER.with(Cell::get);
+        Allocations {
+            count: after.count - before.count,
+            bytes: after.bytes - before.bytes,
+            peak: usize::try_from(after.peak - before.live).unwrap_or_default(),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::allocations::measure;
+
+        #[test]
+        fn test_measure() {
+            let (v, allocations) = measure(|| {
+                let mut v = Vec::<u64>::with_capacity(16);
+                v.extend(0..16);
+                drop(Vec::<u8>::with_capacity(1024));
+                v
+            });
+            let allocations = allocations.unwrap();
+            assert_eq!(v.len(), 16);
+            assert_eq!(allocations.count, 2);
+            assert_eq!(allocations.bytes, 16 * 8 + 1024);
+            assert_eq!(allocations.peak, 16 * 8 + 1024);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use aoc2021::Answer;
+use eyre::{bail, WrapErr};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+    io::ErrorKind,
+};
+
+pub(crate) const ANSWERS: &str = "answers.txt";
+
+const HEADER: &str = "\
+# Known answers, one per day, part, and input set.
+# The input set is a fingerprint of the input the answer belongs to.
+# Answers prefixed with `fnv1a:` are hashed, so that they don't spoil the puzzle.
+# day part input answer
+";
+
+const HASH_PREFIX: &str = "fnv1a:";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Expected {
+    Plain(Answer),
+    Hashed(u64),
+}
+
+impl Expected {
+    /// Hashes the answer as it is displayed.
+    pub(crate) fn hashed(answer: &Answer) -> Self {
+        Self::Hashed(fnv1a(answer.to_string().as_bytes()))
+    }
+
+    fn matches(&self, answer: &Answer) -> bool {
+        match self {
+            Self::Plain(expected) => expected == answer,
+            Self::Hashed(expected) => *expected == fnv1a(answer.to_string().as_bytes()),
+        }
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Plain(answer) => answer.fmt(f),
+            Self::Hashed(hash) => write!(f, "{HASH_PREFIX}{hash:016x}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Verdict {
+    Pass,
+    Fail(Expected),
+    Unknown,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) struct Key {
+    pub(crate) day: u8,
+    pub(crate) part: u8,
+    pub(crate) input: String,
+}
+
+/// The answer file, mapping each day, part, and input set to its expected answer.
+#[derive(Debug, Default)]
+pub(crate) struct Answers(BTreeMap<Key, Expected>);
+
+impl Answers {
+    pub(crate) fn load(path: &str) -> eyre::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e).wrap_err_with(|| format!("Could not read {path}")),
+        };
+        Self::parse(&content).wrap_err_with(|| format!("Could not parse {path}"))
+    }
+
+    fn parse(content: &str) -> eyre::Result<Self> {
+        let mut answers = BTreeMap::new();
+        for (line_no, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(4, char::is_whitespace).map(str::trim);
+            let (Some(day), Some(part), Some(input), Some(answer)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                bail!("line {}: expected `day part input answer`", line_no + 1);
+            };
+
+            let key = Key {
+                day: day
+                    .parse()
+                    .wrap_err_with(|| format!("line {}", line_no + 1))?,
+                part: part
+                    .parse()
+                    .wrap_err_with(|| format!("line {}", line_no + 1))?,
+                input: input.to_string(),
+            };
+            let expected = match answer.strip_prefix(HASH_PREFIX) {
+                Some(hash) => Expected::Hashed(
+                    u64::from_str_radix(hash, 16)
+                        .wrap_err_with(|| format!("line {}", line_no + 1))?,
+                ),
+                None => Expected::Plain(answer.parse().unwrap_or_else(|never| match never {})),
+            };
+            answers.insert(key, expected);
+        }
+        Ok(Self(answers))
+    }
+
+    pub(crate) fn save(&self, path: &str) -> eyre::Result<()> {
+        std::fs::write(path, self.to_string()).wrap_err_with(|| format!("Could not write {path}"))
+    }
+
+    pub(crate) fn check(&self, key: &Key, answer: &Answer) -> Verdict {
+        match self.0.get(key) {
+            Some(expected) if expected.matches(answer) => Verdict::Pass,
+            Some(expected) => Verdict::Fail(expected.clone()),
+            None => Verdict::Unknown,
+        }
+    }
+
+    pub(crate) fn record(&mut self, key: Key, expected: Expected) {
+        self.0.insert(key, expected);
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(HEADER)?;
+        for (Key { day, part, input }, expected) in &self.0 {
+            writeln!(f, "{day:02} {part} {input} {expected}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Identifies an input set by its content, so that answers follow the input they belong to.
+pub(crate) fn fingerprint(input: &str) -> String {
+    format!("{:016x}", fnv1a(input.trim().as_bytes()))
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let key = |part| Key {
+            day: 8,
+            part,
+            input: fingerprint("some input"),
+        };
+        let mut answers = Answers::default();
+        answers.record(key(1), Expected::Plain(Answer::from(534_u32)));
+        answers.record(key(2), Expected::hashed(&Answer::from(1_070_188_u32)));
+        answers.record(key(3), Expected::Plain(Answer::from("EFJKZLBL")));
+
+        let answers = Answers::parse(&answers.to_string()).unwrap();
+        assert_eq!(
+            answers.check(&key(1), &Answer::from(534_u32)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(&key(1), &Answer::from(534_i64)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(&key(3), &Answer::from("EFJKZLBL")),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(&key(2), &Answer::from(1_070_188_u32)),
+            Verdict::Pass
+        );
+        assert_eq!(
+            answers.check(&key(1), &Answer::from(535_u32)),
+            Verdict::Fail(Expected::Plain(Answer::from(534_u32)))
+        );
+        assert!(matches!(
+            answers.check(&key(2), &Answer::from(42_u32)),
+            Verdict::Fail(_)
+        ));
+        assert_eq!(
+            answers.check(&key(4), &Answer::from(42_u32)),
+            Verdict::Unknown
+        );
+    }
+}
@@ -0,0 +1,436 @@
+use crate::{output::Format, scaffold::Shape};
+use aoc2021::{Part, Timing};
+use eyre::{bail, eyre};
+use std::time::Duration;
+
+/// A selection of days, as given on the command line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Days {
+    All,
+    Latest,
+    Day(u8),
+    /// An inclusive range of days.
+    Range(u8, u8),
+}
+
+impl Days {
+    /// Parses `all`, `latest`, `8`, `day08`, `1-5`, `1..5`, `1..=5`, or a comma separated list of those.
+    fn parse(arg: &str) -> eyre::Result<Vec<Self>> {
+        arg.split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|spec| {
+                let day = |s: &str| {
+                    let s = s.trim();
+                    s.strip_prefix("day")
+                        .unwrap_or(s)
+                        .parse::<u8>()
+                        .map_err(|_| eyre!("Unexpected argument {:?}, expected a day", arg))
+                };
+                let range = |from: &str, to: u8| {
+                    let from = day(from)?;
+                    if from > to {
+                        bail!(
+                            "Day range {:?} is empty, expected the first day first",
+                            spec
+                        );
+                    }
+                    Ok(Self::Range(from, to))
+                };
+                Ok(match spec {
+                    "all" => Self::All,
+                    "latest" => Self::Latest,
+                    _ => {
+                        if let Some((from, to)) = spec.split_once("..=") {
+                            range(from, day(to)?)?
+                        } else if let Some((from, to)) = spec.split_once("..") {
+                            range(from, day(to)?.saturating_sub(1))?
+                        } else if let Some((from, to)) = spec.split_once('-') {
+                            range(from, day(to)?)?
+                        } else {
+                            Self::Day(day(spec)?)
+                        }
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+pub(crate) enum Command {
+    Run(Run),
+    Bench(Bench),
+    Readme(Readme),
+    Fetch(Fetch),
+    Verify(Verify),
+    New(New),
+    List,
+}
+
+pub(crate) struct Run {
+    pub(crate) days: Vec<Days>,
+    pub(crate) input: Option<String>,
+    /// Runs on the n-th example from `register!`, counting from 1.
+    pub(crate) example: Option<usize>,
+    pub(crate) part: Option<Part>,
+    pub(crate) format: Format,
+    /// How many days to run at once.
+    pub(crate) jobs: usize,
+    /// How long a day may take before it is given up on.
+    pub(crate) timeout: Option<Duration>,
+    /// Whether parts are timed once, or repeated until their timings are stable.
+    pub(crate) timing: Timing,
+}
+
+pub(crate) struct Bench {
+    pub(crate) days: Vec<Days>,
+    pub(crate) warmup: usize,
+    pub(crate) runs: usize,
+    pub(crate) export_markdown: Option<String>,
+}
+
+pub(crate) struct Readme {
+    pub(crate) template: String,
+    pub(crate) output: String,
+    pub(crate) warmup: usize,
+    pub(crate) runs: usize,
+}
+
+pub(crate) struct Fetch {
+    pub(crate) days: Vec<Days>,
+    pub(crate) force: bool,
+    pub(crate) base_url: String,
+    pub(crate) session_file: String,
+}
+
+pub(crate) struct Verify {
+    pub(crate) days: Vec<Days>,
+    pub(crate) input: Option<String>,
+    pub(crate) answers: String,
+    pub(crate) record: bool,
+    pub(crate) hash: bool,
+}
+
+pub(crate) struct New {
+    pub(crate) day: u8,
+    pub(crate) title: Option<String>,
+    pub(crate) shape: Shape,
+    pub(crate) example: bool,
+    pub(crate) force: bool,
+}
+
+impl Command {
+    pub(crate) fn parse() -> eyre::Result<Self> {
+        let mut args = std::env::args().skip(1).peekable();
+        match args.peek().map(String::as_str) {
+            Some("list") => Ok(Self::List),
+            Some("run") => Run::parse(args.skip(1)).map(Self::Run),
+            Some("bench") => Bench::parse(args.skip(1)).map(Self::Bench),
+            Some("readme") => Readme::parse(args.skip(1)).map(Self::Readme),
+            Some("fetch") => Fetch::parse(args.skip(1)).map(Self::Fetch),
+            Some("verify") => Verify::parse(args.skip(1)).map(Self::Verify),
+            Some("new") => New::parse(args.skip(1)).map(Self::New),
+            _ => Run::parse(args).map(Self::Run),
+        }
+    }
+}
+
+impl Run {
+    fn parse(mut args: impl Iterator<Item = String>) -> eyre::Result<Self> {
+        let mut days = Vec::new();
+        let mut input = None;
+        let mut example = None;
+        let mut part = None;
+        let mut format = Format::Text;
+        let mut jobs = 1;
+        let mut timeout = None;
+        let mut timing = Timing::Once;
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = split_flag(&arg);
+            match flag {
+                "--input" | "-i" => input = Some(value(flag, inline, &mut args)?),
+                "--example" | "-e" => example = Some(count(flag, inline, &mut args)?),
+                "--part" | "-p" => {
+                    part = Some(match value(flag, inline, &mut args)?.as_str() {
+                        "1" => Part::One,
+                        "2" => Part::Two,
+                        other => bail!("Unknown part {:?}, expected 1 or 2", other),
+                    });
+                }
+                "--format" | "-f" => {
+                    let value = value(flag, inline, &mut args)?;
+                    format = value.parse().map_err(|_| {
+                        eyre!("Unknown format {:?}, expected text, json, or csv", value)
+                    })?;
+                }
+                "--jobs" | "-j" => jobs = count(flag, inline, &mut args)?,
+                "--timeout" | "-t" => {
+                    let value = value(flag, inline, &mut args)?;
+                    timeout = Some(humantime::parse_duration(&value).map_err(|e| {
+                        eyre!(
+                            "Invalid timeout {:?}, expected e.g. 5s or 500ms: {}",
+                            value,
+                            e
+                        )
+                    })?);
+                }
+                "--adaptive" | "-a" => {
+                    let value = value(flag, inline, &mut args)?;
+                    let min_time = humantime::parse_duration(&value).map_err(|e| {
+                        eyre!(
+                            "Invalid minimum time {:?}, expected e.g. 1s or 200ms: {}",
+                            value,
+                            e
+                        )
+                    })?;
+                    if min_time.is_zero() {
+                        bail!("--adaptive requires a minimum time above 0");
+                    }
+                    timing = Timing::Adaptive(min_time);
+                }
+                "-" => input = Some(arg.clone()),
+                _ => days.extend(Days::parse(&arg)?),
+            }
+        }
+
+        if jobs == 0 {
+            bail!("--jobs must be at least 1");
+        }
+        if example == Some(0) {
+            bail!("--example counts from 1");
+        }
+        if input.is_some() && example.is_some() {
+            bail!("--input and --example can't be used together");
+        }
+
+        Ok(Self {
+            days,
+            input,
+            example,
+            part,
+            format,
+            jobs,
+            timeout,
+            timing,
+        })
+    }
+}
+
+impl Bench {
+    fn parse(mut args: impl Iterator<Item = String>) -> eyre::Result<Self> {
+        let mut days = Vec::new();
+        let mut warmup = 5;
+        let mut runs = 50;
+        let mut export_markdown = None;
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = split_flag(&arg);
+            match flag {
+                "--warmup" | "-w" => warmup = count(flag, inline, &mut args)?,
+                "--runs" | "-r" => runs = count(flag, inline, &mut args)?,
+                "--export-markdown" => export_markdown = Some(value(flag, inline, &mut args)?),
+                _ => days.extend(Days::parse(&arg)?),
+            }
+        }
+
+        if runs == 0 {
+            bail!("--runs must be at least 1");
+        }
+
+        Ok(Self {
+            days,
+            warmup,
+            runs,
+            export_markdown,
+        })
+    }
+}
+
+impl Readme {
+    fn parse(mut args: impl Iterator<Item = String>) -> eyre::Result<Self> {
+        let mut template = String::from("README.tpl.md");
+        let mut output = String::from("README.md");
+        let mut warmup = 5;
+        let mut runs = 50;
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = split_flag(&arg);
+            match flag {
+                "--template" | "-t" => template = value(flag, inline, &mut args)?,
+                "--output" | "-o" => output = value(flag, inline, &mut args)?,
+                "--warmup" | "-w" => warmup = count(flag, inline, &mut args)?,
+                "--runs" | "-r" => runs = count(flag, inline, &mut args)?,
+                _ => bail!("Unexpected argument {:?}", arg),
+            }
+        }
+
+        if runs == 0 {
+            bail!("--runs must be at least 1");
+        }
+
+        Ok(Self {
+            template,
+            output,
+            warmup,
+            runs,
+        })
+    }
+}
+
+impl Fetch {
+    fn parse(mut args: impl Iterator<Item = String>) -> eyre::Result<Self> {
+        let mut days = Vec::new();
+        let mut force = false;
+        let mut base_url = String::from(crate::fetch::BASE_URL);
+        let mut session_file = String::from(".sessioncookie");
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = split_flag(&arg);
+            match flag {
+                "--force" | "-f" => force = true,
+                "--base-url" => base_url = value(flag, inline, &mut args)?,
+                "--session-file" => session_file = value(flag, inline, &mut args)?,
+                _ => days.extend(Days::parse(&arg)?),
+            }
+        }
+
+        if days.is_empty() {
+            bail!("fetch requires a day, or all");
+        }
+
+        Ok(Self {
+            days,
+            force,
+            base_url,
+            session_file,
+        })
+    }
+}
+
+impl Verify {
+    fn parse(mut args: impl Iterator<Item = String>) -> eyre::Result<Self> {
+        let mut days = Vec::new();
+        let mut input = None;
+        let mut answers = String::from(crate::answers::ANSWERS);
+        let mut record = false;
+        let mut hash = false;
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = split_flag(&arg);
+            match flag {
+                "--input" | "-i" => input = Some(value(flag, inline, &mut args)?),
+                "--answers" | "-a" => answers = value(flag, inline, &mut args)?,
+                "--record" => record = true,
+                "--hash" => hash = true,
+                "-" => input = Some(arg.clone()),
+                _ => days.extend(Days::parse(&arg)?),
+            }
+        }
+
+        Ok(Self {
+            days,
+            input,
+            answers,
+            record,
+            hash,
+        })
+    }
+}
+
+impl New {
+    fn parse(mut args: impl Iterator<Item = String>) -> eyre::Result<Self> {
+        let mut day = None;
+        let mut title = None;
+        let mut shape = Shape::Parse;
+        let mut example = false;
+        let mut force = false;
+
+        while let Some(arg) = args.next() {
+            let (flag, inline) = split_flag(&arg);
+            match flag {
+                "--title" | "-t" => title = Some(value(flag, inline, &mut args)?),
+                "--shape" | "-s" => {
+                    let value = value(flag, inline, &mut args)?;
+                    shape = value.parse().map_err(|_| {
+                        eyre!(
+                            "Unknown shape {:?}, expected parse, chunk, verbatim, grid, bytes, or csv",
+                            value
+                        )
+                    })?;
+                }
+                "--example" | "-e" => example = true,
+                "--force" | "-f" => force = true,
+                _ => match (day, arg.parse::<u8>()) {
+                    (None, Ok(d)) => day = Some(d),
+                    _ => bail!("Unexpected argument {:?}", arg),
+                },
+            }
+        }
+
+        let day = day.ok_or_else(|| eyre!("new requires a day"))?;
+        Ok(Self {
+            day,
+            title,
+            shape,
+            example,
+            force,
+        })
+    }
+}
+
+/// Splits `--flag=value` into the flag and its inline value.
+fn split_flag(arg: &str) -> (&str, Option<&str>) {
+    match arg.split_once('=') {
+        Some((flag, value)) if flag.starts_with("--") => (flag, Some(value)),
+        _ => (arg, None),
+    }
+}
+
+/// Returns the inline value of a flag, or takes the next argument.
+fn value(
+    flag: &str,
+    inline: Option<&str>,
+    args: &mut impl Iterator<Item = String>,
+) -> eyre::Result<String> {
+    inline
+        .map(String::from)
+        .or_else(|| args.next())
+        .ok_or_else(|| eyre!("{} requires a value", flag))
+}
+
+fn count(
+    flag: &str,
+    inline: Option<&str>,
+    args: &mut impl Iterator<Item = String>,
+) -> eyre::Result<usize> {
+    let value = value(flag, inline, args)?;
+    value
+        .parse()
+        .map_err(|_| eyre!("{} requires a number, got {:?}", flag, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_days() {
+        assert_eq!(Days::parse("8").unwrap(), [Days::Day(8)]);
+        assert_eq!(Days::parse("day08").unwrap(), [Days::Day(8)]);
+        assert_eq!(Days::parse("1..=10").unwrap(), [Days::Range(1, 10)]);
+        assert_eq!(Days::parse("1..10").unwrap(), [Days::Range(1, 9)]);
+        assert_eq!(
+            Days::parse("1-5,8").unwrap(),
+            [Days::Range(1, 5), Days::Day(8)]
+        );
+        assert_eq!(
+            Days::parse("all,latest").unwrap(),
+            [Days::All, Days::Latest]
+        );
+        assert!(Days::parse("--nope").is_err());
+        assert!(Days::parse("10-6").is_err());
+        assert!(Days::parse("6..6").is_err());
+        assert_eq!(Days::parse("6-6").unwrap(), [Days::Range(6, 6)]);
+    }
+}
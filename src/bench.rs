@@ -0,0 +1,224 @@
+use crate::output::{markdown_table, Align};
+use aoc2021::{Day, ParseError, Timing};
+use std::time::{Duration, Instant};
+
+/// Summary statistics over a number of samples, in seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Stats {
+    pub(crate) mean: f64,
+    pub(crate) stddev: f64,
+    pub(crate) median: f64,
+    pub(crate) min: f64,
+    pub(crate) max: f64,
+}
+
+impl Stats {
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn of(samples: &[Duration]) -> Option<Self> {
+        let mut secs = samples
+            .iter()
+            .map(Duration::as_secs_f64)
+            .collect::<Vec<_>>();
+        secs.sort_by(f64::total_cmp);
+        let (&min, &max) = (secs.first()?, secs.last()?);
+
+        let n = secs.len() as f64;
+        let mean = secs.iter().sum::<f64>() / n;
+        let variance = if secs.len() > 1 {
+            secs.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        let half = secs.len() / 2;
+        let median = if secs.len() % 2 == 0 {
+            (secs[half - 1] + secs[half]) / 2.0
+        } else {
+            secs[half]
+        };
+
+        Some(Self {
+            mean,
+            stddev: variance.sqrt(),
+            median,
+            min,
+            max,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Unit {
+    Seconds,
+    Millis,
+    Micros,
+}
+
+impl Unit {
+    pub(crate) fn of(secs: f64) -> Self {
+        if secs >= 1.0 {
+            Self::Seconds
+        } else if secs >= 1e-3 {
+            Self::Millis
+        } else {
+            Self::Micros
+        }
+    }
+
+    pub(crate) fn scale(self, secs: f64) -> f64 {
+        match self {
+            Self::Seconds => secs,
+            Self::Millis => secs * 1e3,
+            Self::Micros => secs * 1e6,
+        }
+    }
+
+    pub(crate) fn suffix(self) -> &'static str {
+        match self {
+            Self::Seconds => "s",
+            Self::Millis => "ms",
+            Self::Micros => "µs",
+        }
+    }
+}
+
+/// The timings of repeated runs of one day.
+pub(crate) struct Measurement {
+    pub(crate) day: u8,
+    pub(crate) runs: usize,
+    pub(crate) parse: Option<Stats>,
+    pub(crate) part1: Option<Stats>,
+    pub(crate) part2: Option<Stats>,
+    /// The whole in-process run, including the parts that are not timed separately.
+    pub(crate) total: Stats,
+}
+
+pub(crate) fn measure(day: &Day, warmup: usize, runs: usize) -> Result<Measurement, ParseError> {
+    for _ in 0..warmup {
+        day.solve(Timing::Once)?;
+    }
+
+    let mut parse = Vec::with_capacity(runs);
+    let mut part1 = Vec::with_capacity(runs);
+    let mut part2 = Vec::with_capacity(runs);
+    let mut total = Vec::with_capacity(runs);
+
+    for _ in 0..runs {
+        let start = Instant::now();
+        let solution = day.solve(Timing::Once)?;
+        total.push(start.elapsed());
+
+        parse.extend(solution.parse_time);
+        if let Some((time1, time2)) = solution.timings {
+            part1.push(time1);
+            part2.push(time2);
+        }
+    }
+
+    Ok(Measurement {
+        day: day.day,
+        runs,
+        parse: Stats::of(&parse),
+        part1: Stats::of(&part1),
+        part2: Stats::of(&part2),
+        total: Stats::of(&total).expect("at least one run"),
+    })
+}
+
+pub(crate) fn print(measurement: &Measurement) {
+    let phases = [
+        ("Parse", measurement.parse),
+        ("Part 1", measurement.part1),
+        ("Part 2", measurement.part2),
+        ("Total", Some(measurement.total)),
+    ];
+    for (phase, stats) in phases {
+        let Some(stats) = stats else { continue };
+        let unit = Unit::of(stats.mean);
+        let fmt = |secs| format!("{:.1} {}", unit.scale(secs), unit.suffix());
+        println!(
+            "Day {:02} {}:\t{} ± {} (median {}, min {}, max {}, {} runs)",
+            measurement.day,
+            phase,
+            fmt(stats.mean),
+            fmt(stats.stddev),
+            fmt(stats.median),
+            fmt(stats.min),
+            fmt(stats.max),
+            measurement.runs
+        );
+    }
+}
+
+/// Renders the measurements in the layout of the runtime table in the README.
+pub(crate) fn markdown(measurements: &[Measurement]) -> String {
+    let fastest = measurements
+        .iter()
+        .map(|m| m.total)
+        .min_by(|a, b| a.mean.total_cmp(&b.mean));
+    let Some(fastest) = fastest else {
+        return String::new();
+    };
+
+    let unit = Unit::of(fastest.mean);
+    let header = |name: &str| format!("{name} [{}]", unit.suffix());
+    let header = [
+        (String::from("Command"), Align::Left),
+        (header("Mean"), Align::Right),
+        (header("Min"), Align::Right),
+        (header("Max"), Align::Right),
+        (String::from("Relative"), Align::Right),
+        (header("Parse"), Align::Right),
+        (header("Part 1"), Align::Right),
+        (header("Part 2"), Align::Right),
+    ];
+
+    let mean = |stats: Option<Stats>| {
+        stats.map_or_else(String::new, |s| {
+            format!("{:.1} ± {:.1}", unit.scale(s.mean), unit.scale(s.stddev))
+        })
+    };
+
+    let rows = measurements
+        .iter()
+        .map(|m| {
+            let total = m.total;
+            let relative = total.mean / fastest.mean;
+            let relative = if total == fastest {
+                String::from("1.00")
+            } else {
+                let error =
+                    relative * (total.stddev / total.mean).hypot(fastest.stddev / fastest.mean);
+                format!("{relative:.2} ± {error:.2}")
+            };
+            vec![
+                format!("`day {}`", m.day),
+                mean(Some(total)),
+                format!("{:.1}", unit.scale(total.min)),
+                format!("{:.1}", unit.scale(total.max)),
+                relative,
+                mean(m.parse),
+                mean(m.part1),
+                mean(m.part2),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    markdown_table(&header, &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_stats() {
+        let samples = [4, 1, 3, 2].map(Duration::from_secs);
+        let stats = Stats::of(&samples).unwrap();
+        assert_eq!(stats.mean, 2.5);
+        assert_eq!(stats.median, 2.5);
+        assert_eq!((stats.min, stats.max), (1.0, 4.0));
+        assert!((stats.stddev - 1.290_994).abs() < 1e-6);
+        assert_eq!(Stats::of(&[]), None);
+    }
+}
@@ -1,9 +1,28 @@
 register!(
-    "input/day1.txt";
+    1, "Sonar Sweep", "input/day1.txt";
     (input: parse u64) -> usize {
         part1(&input);
         part2(&input);
     }
+    examples: [
+        (
+            r#"
+            199
+            200
+            208
+            210
+            200
+            207
+            240
+            269
+            260
+            263
+            "#,
+            7,
+            5,
+        ),
+    ];
+    answers: (1665, 1702);
 );
 
 fn part1(items: &[u64]) -> usize {
@@ -18,24 +37,3 @@ fn part2(items: &[u64]) -> usize {
         .filter(|[a, _, _, d]| d > a)
         .count()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_ex() {
-        let items = vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
-        let (res1, res2) = Solver::run(items);
-        assert_eq!(res1, 7);
-        assert_eq!(res2, 5);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 1665);
-        assert_eq!(res2, 1702);
-    }
-}
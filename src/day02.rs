@@ -1,12 +1,24 @@
-use parse_display::FromStr;
-use std::convert::Infallible;
-
 register!(
-    "input/day2.txt";
+    2, "Dive!", "input/day2.txt";
     (input: parse Command) -> i64 {
         part1(&input);
         part2(&input);
     }
+    examples: [
+        (
+            r#"
+            forward 5
+            down 5
+            forward 8
+            up 3
+            down 8
+            forward 2
+            "#,
+            150,
+            900,
+        ),
+    ];
+    answers: (1_698_735, 1_594_785_890);
 );
 
 #[allow(clippy::use_self)]
@@ -17,22 +29,46 @@ pub enum Direction {
     Up,
 }
 
+#[derive(Debug, thiserror::Error)]
+#[error("unknown direction {0:?}")]
+pub struct UnknownDirection(String);
+
 impl std::str::FromStr for Direction {
-    type Err = Infallible;
+    type Err = UnknownDirection;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
             "forward" => Self::Forward,
             "down" => Self::Down,
             "up" => Self::Up,
-            _ => unreachable!(),
+            _ => return Err(UnknownDirection(s.to_string())),
         })
     }
 }
 
-#[derive(Clone, Copy, Debug, FromStr)]
-#[display("{0} {1}")]
+#[derive(Debug, thiserror::Error)]
+pub enum InvalidCommand {
+    #[error("expected a direction and a distance")]
+    Incomplete,
+    #[error(transparent)]
+    Direction(#[from] UnknownDirection),
+    #[error("invalid distance {0:?}: {1}")]
+    Distance(String, std::num::ParseIntError),
+}
+
+#[derive(Clone, Copy, Debug)]
 pub struct Command(Direction, i64);
 
+impl std::str::FromStr for Command {
+    type Err = InvalidCommand;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (direction, distance) = s.split_once(' ').ok_or(InvalidCommand::Incomplete)?;
+        let distance = distance
+            .parse()
+            .map_err(|e| InvalidCommand::Distance(distance.to_string(), e))?;
+        Ok(Self(direction.parse()?, distance))
+    }
+}
+
 fn part1(items: &[Command]) -> i64 {
     let (mut horizontal, mut depth) = (0, 0);
     for Command(direction, unit) in items {
@@ -59,31 +95,3 @@ fn part2(items: &[Command]) -> i64 {
     }
     horizontal * depth
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_ex() {
-        let items = r#"
-forward 5
-down 5
-forward 8
-up 3
-down 8
-forward 2
-"#;
-        let (res1, res2) = Solver::run_on(items);
-        assert_eq!(res1, 150);
-        assert_eq!(res2, 900);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 1698735);
-        assert_eq!(res2, 1594785890);
-    }
-}
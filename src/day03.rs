@@ -1,14 +1,35 @@
 use derive_more::Deref;
-use num_enum::TryFromPrimitive;
+use num_enum::{TryFromPrimitive, TryFromPrimitiveError};
 use std::ops::AddAssign;
 use tap::Tap;
 
 register!(
-    "input/day3.txt";
+    3, "Binary Diagnostic", "input/day3.txt";
     (input: Bits) -> u64 {
         part1(&input);
         part2(&mut input);
     }
+    examples: [
+        (
+            r#"
+            00100
+            11110
+            10110
+            10111
+            10101
+            01111
+            00111
+            11100
+            10000
+            11001
+            00010
+            01010
+            "#,
+            198,
+            230,
+        ),
+    ];
+    answers: (4_160_394, 4_125_600);
 );
 
 #[derive(Clone, Copy, Debug, TryFromPrimitive)]
@@ -27,7 +48,7 @@ impl AddAssign<Bit> for isize {
     }
 }
 
-#[derive(Debug, Deref)]
+#[derive(Clone, Debug, Deref)]
 pub struct Bits(Vec<Bit>);
 
 impl FromIterator<Bit> for Bits {
@@ -36,9 +57,11 @@ impl FromIterator<Bit> for Bits {
     }
 }
 
-impl From<String> for Bits {
-    fn from(s: String) -> Self {
-        s.bytes().map(|b| Bit::try_from(b).unwrap()).collect()
+impl TryFrom<&str> for Bits {
+    type Error = TryFromPrimitiveError<Bit>;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.bytes().map(Bit::try_from).collect()
     }
 }
 
@@ -107,37 +130,3 @@ fn find_rating_2(mut items: &mut [Bits], select: impl Fn(isize) -> bool) -> u64
 
     unreachable!()
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_ex() {
-        let items = r#"
-00100
-11110
-10110
-10111
-10101
-01111
-00111
-11100
-10000
-11001
-00010
-01010
-"#;
-        let (res1, res2) = Solver::run_on(items);
-        assert_eq!(res1, 198);
-        assert_eq!(res2, 230);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 4160394);
-        assert_eq!(res2, 4125600);
-    }
-}
@@ -1,12 +1,40 @@
-use aoc2021::{lines, PuzzleInput};
+use aoc2021::{Blocks, Csv, Header, ParseError, PuzzleInput};
 use derive_more::{Deref, DerefMut};
 
 register!(
-    "input/day4.txt";
-    (input: verbatim Bingo) -> u32 {
-        part1(&input.draws, input.boards.clone());
-        part2(&input.draws, input.boards);
+    4, "Giant Squid", "input/day4.txt";
+    (input: verbatim Header<Csv<u8>, Blocks<Board>>) -> u32 {
+        part1(&input.0, input.1.clone());
+        part2(&input.0, input.1);
     }
+    examples: [
+        (
+            r#"
+            7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+            22 13 17 11  0
+             8  2 23  4 24
+            21  9 14 16  7
+             6 10  3 18  5
+             1 12 20 15 19
+
+             3 15  0  2 22
+             9 18 13 17  5
+            19  8  7 25 23
+            20 11 10 24  4
+            14 21 16 12  6
+
+            14 21 17 24  4
+            10 16 15  9 19
+            18  8 23 26 20
+            22 11 13  6  5
+             2  0 12  3  7
+            "#,
+            4512,
+            1924,
+        ),
+    ];
+    answers: (32844, 4920);
 );
 
 fn part1(draws: &[u8], boards: Vec<Board>) -> u32 {
@@ -37,7 +65,7 @@ fn draw(boards: &mut Vec<Board>, number: u8) -> Vec<u32> {
 const BOARD_SIZE: usize = 5;
 
 #[derive(Clone, Copy)]
-struct Board([u8; BOARD_SIZE * BOARD_SIZE], u32);
+pub struct Board([u8; BOARD_SIZE * BOARD_SIZE], u32);
 
 impl Board {
     fn draw(&mut self, number: u8) -> bool {
@@ -84,74 +112,22 @@ fn winners() -> &'static [u32] {
     WINNERS.as_ref()
 }
 
-pub struct Bingo {
-    draws: Vec<u8>,
-    boards: Vec<Board>,
-}
-
-impl PuzzleInput for Bingo {
-    type Out = Self;
-
-    fn from_input(input: &str) -> Self::Out {
-        let mut blocks = input.split("\n\n");
-
-        let draws = blocks.next().unwrap();
-        let draws = lines(draws)
-            .flat_map(|s| s.split(','))
-            .flat_map(str::parse::<u8>)
-            .collect();
-
-        let boards = blocks
-            .map(|block| {
-                lines(block)
-                    .flat_map(str::split_ascii_whitespace)
-                    .flat_map(str::parse::<u8>)
-                    .collect::<Vec<_>>()
-            })
-            .map(|numbers| Board(numbers.try_into().unwrap(), 0))
-            .collect();
-
-        Self { draws, boards }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_ex() {
-        let input = r#"
-        7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
-
-        22 13 17 11  0
-         8  2 23  4 24
-        21  9 14 16  7
-         6 10  3 18  5
-         1 12 20 15 19
-
-         3 15  0  2 22
-         9 18 13 17  5
-        19  8  7 25 23
-        20 11 10 24  4
-        14 21 16 12  6
-
-        14 21 17 24  4
-        10 16 15  9 19
-        18  8 23 26 20
-        22 11 13  6  5
-         2  0 12  3  7
-        "#;
-        let (res1, res2) = Solver::run_on(input);
-        assert_eq!(res1, 4512);
-        assert_eq!(res2, 1924);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 32844);
-        assert_eq!(res2, 4920);
+impl PuzzleInput for Board {
+    type Out<'a> = Self;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        let numbers = input
+            .split_ascii_whitespace()
+            .map(|n| n.parse::<u8>().map_err(|e| ParseError::new(input, n, e)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let numbers = numbers.try_into().map_err(|numbers: Vec<_>| {
+            let message = format!(
+                "expected a board of {} numbers, got {}",
+                BOARD_SIZE * BOARD_SIZE,
+                numbers.len()
+            );
+            ParseError::new(input, input, message)
+        })?;
+        Ok(Self(numbers, 0))
     }
 }
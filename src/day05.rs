@@ -1,12 +1,31 @@
-use parse_display::FromStr;
+use aoc2021::{Fields, FromCaptures, ParseError};
 use std::{collections::HashMap, iter::repeat};
 
 register!(
-    "input/day5.txt";
-    (input: parse VentLine) -> usize {
+    5, "Hydrothermal Venture", "input/day5.txt";
+    (input: matching VentLine) -> usize {
         part1(&input);
         part2(&input);
     }
+    examples: [
+        (
+            r#"
+            0,9 -> 5,9
+            8,0 -> 0,8
+            9,4 -> 3,4
+            2,2 -> 2,1
+            7,0 -> 7,4
+            6,4 -> 2,0
+            0,9 -> 2,9
+            3,4 -> 1,4
+            0,0 -> 8,8
+            5,5 -> 8,2
+            "#,
+            5,
+            12,
+        ),
+    ];
+    answers: (4873, 19472);
 );
 
 fn part1(items: &[VentLine]) -> usize {
@@ -32,8 +51,7 @@ fn solve(items: &[VentLine], include_diagonal: bool) -> usize {
     covered.into_iter().filter(|(_, count)| *count >= 2).count()
 }
 
-#[derive(Clone, Copy, Debug, FromStr)]
-#[display("{x1},{y1} -> {x2},{y2}")]
+#[derive(Clone, Copy, Debug)]
 pub struct VentLine {
     x1: i32,
     y1: i32,
@@ -41,34 +59,15 @@ pub struct VentLine {
     y2: i32,
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
+impl FromCaptures for VentLine {
+    const PATTERN: &'static str = r"(?P<x1>\d+),(?P<y1>\d+) -> (?P<x2>\d+),(?P<y2>\d+)";
 
-    #[test]
-    fn test_ex() {
-        let input = r#"
-        0,9 -> 5,9
-        8,0 -> 0,8
-        9,4 -> 3,4
-        2,2 -> 2,1
-        7,0 -> 7,4
-        6,4 -> 2,0
-        0,9 -> 2,9
-        3,4 -> 1,4
-        0,0 -> 8,8
-        5,5 -> 8,2
-        "#;
-        let (res1, res2) = Solver::run_on(input);
-        assert_eq!(res1, 5);
-        assert_eq!(res2, 12);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 4873);
-        assert_eq!(res2, 19472);
+    fn from_captures(captures: &Fields<'_>) -> Result<Self, ParseError> {
+        Ok(Self {
+            x1: captures.parse("x1")?,
+            y1: captures.parse("y1")?,
+            x2: captures.parse("x2")?,
+            y2: captures.parse("y2")?,
+        })
     }
 }
@@ -1,11 +1,13 @@
-use std::{convert::Infallible, str::FromStr};
-
 register!(
-    "input/day6.txt";
-    (input: parse Fishes) -> usize {
-        part1(&input[0].0);
-        part2(&input[0].0);
+    6, "Lanternfish", "input/day6.txt";
+    (input: csv usize) -> usize {
+        part1(&input);
+        part2(&input);
     }
+    examples: [
+        (r#"3,4,3,1,2"#, 5934, 26_984_457_539),
+    ];
+    answers: (360_610, 1_631_629_590_423);
 );
 
 fn part1(items: &[usize]) -> usize {
@@ -30,34 +32,3 @@ fn model_fishes(initial: &[usize], days: usize) -> usize {
 
     fishes.into_iter().sum()
 }
-
-pub struct Fishes(Vec<usize>);
-
-impl FromStr for Fishes {
-    type Err = Infallible;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(s.split(',').flat_map(str::parse::<usize>).collect()))
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_ex() {
-        let input = r#"3,4,3,1,2"#;
-        let (res1, res2) = Solver::run_on(input);
-        assert_eq!(res1, 5934);
-        assert_eq!(res2, 26984457539);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 360610);
-        assert_eq!(res2, 1631629590423);
-    }
-}
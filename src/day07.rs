@@ -1,11 +1,13 @@
-use std::{convert::Infallible, str::FromStr};
-
 register!(
-    "input/day7.txt";
-    (input: parse Ferrises) -> u32 {
-        part1(&input[0].0);
-        part2(&input[0].0);
+    7, "The Treachery of Whales", "input/day7.txt";
+    (input: csv i32) -> u32 {
+        part1(&input);
+        part2(&input);
     }
+    examples: [
+        (r#"16,1,2,0,4,2,7,1,2,14"#, 37, 168),
+    ];
+    answers: (344_535, 95_581_659);
 );
 
 fn part1(items: &[i32]) -> u32 {
@@ -29,34 +31,3 @@ fn solve(items: &[i32], cost: impl Fn(u32) -> u32) -> u32 {
         .min()
         .unwrap()
 }
-
-pub struct Ferrises(Vec<i32>);
-
-impl FromStr for Ferrises {
-    type Err = Infallible;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(s.split(',').flat_map(str::parse::<i32>).collect()))
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_ex() {
-        let input = r#"16,1,2,0,4,2,7,1,2,14"#;
-        let (res1, res2) = Solver::run_on(input);
-        assert_eq!(res1, 37);
-        assert_eq!(res2, 168);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 344535);
-        assert_eq!(res2, 95581659);
-    }
-}
@@ -1,12 +1,31 @@
-use parse_display::FromStr;
-use std::{convert::Infallible, str::FromStr};
+use std::str::FromStr;
 
 register!(
-    "input/day8.txt";
+    8, "Seven Segment Search", "input/day8.txt";
     (input: parse Input) -> usize {
         part1(&input);
         part2(&mut input);
     }
+    examples: [
+        (r#"acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf"#, 0, 5353),
+        (
+            r#"
+            be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+            edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+            fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+            fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+            aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+            fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+            dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+            bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+            egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+            gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
+            "#,
+            26,
+            61229,
+        ),
+    ];
+    answers: (534, 1_070_188);
 );
 
 fn part1(items: &[Input]) -> usize {
@@ -50,13 +69,32 @@ fn part2(items: &mut [Input]) -> usize {
         .sum()
 }
 
-#[derive(Clone, Debug, FromStr)]
-#[display("{test} | {output}")]
+#[derive(Clone, Debug)]
 pub struct Input {
     test: Digits,
     output: Digits,
 }
 
+impl FromStr for Input {
+    type Err = InvalidNote;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (test, output) = s.split_once(" | ").ok_or(InvalidNote::Separator)?;
+        Ok(Self {
+            test: test.parse()?,
+            output: output.parse()?,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum InvalidNote {
+    #[error("expected patterns and output separated by \" | \"")]
+    Separator,
+    #[error("unknown segment {0:?} in pattern {1:?}")]
+    Segment(char, String),
+}
+
 #[derive(Clone, Debug)]
 struct Digits(Vec<Digit>);
 
@@ -75,10 +113,13 @@ impl Digits {
 }
 
 impl FromStr for Digits {
-    type Err = Infallible;
+    type Err = InvalidNote;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(s.split_ascii_whitespace().map(Digit::from).collect()))
+        s.split_ascii_whitespace()
+            .map(Digit::try_from)
+            .collect::<Result<_, _>>()
+            .map(Self)
     }
 }
 
@@ -99,48 +140,15 @@ impl Digit {
     }
 }
 
-impl From<&'_ str> for Digit {
-    fn from(s: &'_ str) -> Self {
-        Self(s.bytes().map(|b| b - b'a').fold(0, |n, b| n | (1 << b)))
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_small() {
-        let input = r#"acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf"#;
-        let (res1, res2) = Solver::run_on(input);
-        assert_eq!(res1, 0);
-        assert_eq!(res2, 5353);
-    }
-
-    #[test]
-    fn test_ex() {
-        let input = r#"
-        be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
-        edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
-        fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
-        fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
-        aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
-        fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
-        dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
-        bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
-        egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
-        gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
-        "#;
-        let (res1, res2) = Solver::run_on(input);
-        assert_eq!(res1, 26);
-        assert_eq!(res2, 61229);
-    }
+impl TryFrom<&'_ str> for Digit {
+    type Error = InvalidNote;
 
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 534);
-        assert_eq!(res2, 1070188);
+    fn try_from(s: &'_ str) -> Result<Self, Self::Error> {
+        s.chars()
+            .try_fold(0, |n, c| match c {
+                'a'..='g' => Ok(n | (1 << (c as u8 - b'a'))),
+                _ => Err(InvalidNote::Segment(c, s.to_string())),
+            })
+            .map(Self)
     }
 }
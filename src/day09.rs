@@ -1,4 +1,4 @@
-use aoc2021::{lines, PuzzleInput};
+use aoc2021::{Grid, ParseError, PuzzleInput};
 use derive_more::Add;
 use disjoint_sets::UnionFind;
 use fxhash::{FxBuildHasher, FxHashMap};
@@ -7,11 +7,25 @@ use std::{cmp::Reverse, ops::AddAssign};
 pub type Wcc = FxHashMap<usize, Basin>;
 
 register!(
-    "input/day9.txt";
+    9, "Smoke Basin", "input/day9.txt";
     (wcc: verbatim WccInput) -> u64 {
         part1(&wcc);
         part2(&wcc);
     }
+    examples: [
+        (
+            r#"
+            2199943210
+            3987894921
+            9856789892
+            8767896789
+            9899965678
+            "#,
+            15,
+            1134,
+        ),
+    ];
+    answers: (564, 1_038_240);
 );
 
 fn part1(hm: &Wcc) -> u64 {
@@ -58,32 +72,29 @@ impl Default for Basin {
 pub struct WccInput;
 
 impl PuzzleInput for WccInput {
-    type Out = Wcc;
+    type Out<'a> = Wcc;
 
-    fn from_input(input: &str) -> Self::Out {
-        let input = lines(input).map(str::as_bytes).collect::<Vec<_>>();
-        let (h, w) = (input.len(), input[0].len());
-        let size = w * h;
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        let heights = Grid::<u8>::try_from_input(input)?;
+        let (w, size) = (heights.width(), heights.cells().len());
 
         let mut dss = UnionFind::new(size + 1);
-        for (row, current_row) in input.iter().copied().enumerate() {
-            for (col, h) in current_row.iter().map(|b| *b - b'0').enumerate() {
-                let idx = w * row + col;
-                if h == 9 {
-                    // all 9ers are in one community outside of the id range
-                    dss.union(idx, size);
-                    continue;
-                }
+        for (idx, &h) in heights.cells().iter().enumerate() {
+            let (row, col) = heights.position(idx);
+            if h == 9 {
+                // all 9ers are in one community outside of the id range
+                dss.union(idx, size);
+                continue;
+            }
 
-                if let Some(pr) = row.checked_sub(1) {
-                    if input[pr][col] - b'0' != 9 {
-                        dss.union(idx, w * pr + col);
-                    }
+            if let Some(pr) = row.checked_sub(1) {
+                if heights[(pr, col)] != 9 {
+                    dss.union(idx, w * pr + col);
                 }
-                if let Some(pc) = col.checked_sub(1) {
-                    if current_row[pc] - b'0' != 9 {
-                        dss.union(idx, w * row + pc);
-                    }
+            }
+            if let Some(pc) = col.checked_sub(1) {
+                if heights[(row, pc)] != 9 {
+                    dss.union(idx, w * row + pc);
                 }
             }
         }
@@ -97,43 +108,12 @@ impl PuzzleInput for WccInput {
                 continue;
             }
 
-            let row = idx / w;
-            let col = idx % w;
-            let h = input[row][col] - b'0';
-
-            *basins.entry(root).or_default() += h;
+            *basins.entry(root).or_default() += heights[idx];
         }
 
-        basins
+        Ok(basins)
     }
 }
 
 #[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, Add)]
 struct Pos(i16, i16);
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_ex() {
-        let input = r#"
-        2199943210
-        3987894921
-        9856789892
-        8767896789
-        9899965678
-        "#;
-        let (res1, res2) = Solver::run_on(input);
-        assert_eq!(res1, 15);
-        assert_eq!(res2, 1134);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 564);
-        assert_eq!(res2, 1038240);
-    }
-}
@@ -1,14 +1,33 @@
 use aoc2021::MedianExt;
 
 register!(
-    "input/day10.txt";
-    (input: String) -> u64 {
+    10, "Syntax Scoring", "input/day10.txt";
+    (input: lines) -> u64 {
         part1(&input);
         part2(&input);
     }
+    examples: [
+        (
+            r#"
+            [({(<(())[]>[[{[]{<()<>>
+            [(()[<>])]({[<{<<[]>>(
+            {([(<{}[<>[]}>{[]{[(<()>
+            (((({<>}<{<{<>}{[]{[]{}
+            [[<[([]))<([[{}[[()]]]
+            [{[{({}]{}}([{[{{{}}([]
+            {<[[]]>}<{[{[{[]{()[[[]
+            [<(<(<(<{}))><([]([]()
+            <{([([[(<>()){}]>(<<{{
+            <{([{{}}[<[[[<>{}]]]>[]]
+            "#,
+            26397,
+            288_957,
+        ),
+    ];
+    answers: (394_647, 2_380_061_249);
 );
 
-fn part1(items: &[String]) -> u64 {
+fn part1(items: &[&str]) -> u64 {
     items
         .iter()
         .filter_map(|l| parse(l).err())
@@ -22,7 +41,7 @@ fn part1(items: &[String]) -> u64 {
         .sum()
 }
 
-fn part2(items: &[String]) -> u64 {
+fn part2(items: &[&str]) -> u64 {
     items
         .iter()
         .filter_map(|l| parse(l).ok())
@@ -79,35 +98,3 @@ fn parse(bytes: impl AsRef<[u8]>) -> Result<String, u8> {
         Ok(rest) => unreachable!("Incomplete parse, remaining: {:?}", rest),
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_ex() {
-        let input = r#"
-        [({(<(())[]>[[{[]{<()<>>
-        [(()[<>])]({[<{<<[]>>(
-        {([(<{}[<>[]}>{[]{[(<()>
-        (((({<>}<{<{<>}{[]{[]{}
-        [[<[([]))<([[{}[[()]]]
-        [{[{({}]{}}([{[{{{}}([]
-        {<[[]]>}<{[{[{[]{()[[[]
-        [<(<(<(<{}))><([]([]()
-        <{([([[(<>()){}]>(<<{{
-        <{([{{}}[<[[[<>{}]]]>[]]
-        "#;
-        let (res1, res2) = Solver::run_on(input);
-        assert_eq!(res1, 26397);
-        assert_eq!(res2, 288957);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 394647);
-        assert_eq!(res2, 2380061249);
-    }
-}
@@ -1,9 +1,13 @@
 register!(
-    "input/day15.txt";
-    (input: parse u64) -> usize {
+    15, "Chiton", "input/day15.txt";
+    pending (input: parse u64) -> usize {
         part1(&input);
         part2(&input);
     }
+    examples: [
+        (r#""#, 0, 0),
+    ];
+    answers: (0, 0);
 );
 
 fn part1(items: &[u64]) -> usize {
@@ -13,24 +17,3 @@ fn part1(items: &[u64]) -> usize {
 fn part2(items: &[u64]) -> usize {
     0
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use aoc2021::Solution;
-
-    #[test]
-    fn test_ex() {
-        let input = r#""#;
-        let (res1, res2) = Solver::run_on(input);
-        assert_eq!(res1, 0);
-        assert_eq!(res2, 0);
-    }
-
-    #[test]
-    fn test() {
-        let (res1, res2) = Solver::run_on_input();
-        assert_eq!(res1, 0);
-        assert_eq!(res2, 0);
-    }
-}
@@ -0,0 +1,146 @@
+use eyre::{bail, eyre, WrapErr};
+use std::{
+    io::Write,
+    path::Path,
+    process::{Command, Stdio},
+};
+
+pub(crate) const BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = concat!(
+    env!("CARGO_PKG_NAME"),
+    "/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+pub(crate) enum Fetched {
+    Written(usize),
+    Cached,
+}
+
+/// Downloads the input of a day into `path`, unless that file already has content. The session
+/// cookie is only asked for once a download is needed.
+pub(crate) fn fetch(
+    day: u8,
+    path: &Path,
+    base_url: &str,
+    session: impl FnOnce() -> eyre::Result<String>,
+    force: bool,
+) -> eyre::Result<Fetched> {
+    let cached = std::fs::metadata(path).map_or(0, |m| m.len());
+    if cached > 0 && !force {
+        return Ok(Fetched::Cached);
+    }
+
+    let session = session()?;
+    let url = format!("{}/2021/day/{day}/input", base_url.trim_end_matches('/'));
+    // The session cookie is passed on stdin rather than as an argument, as the arguments of a
+    // process can be read by every other user.
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--user-agent", USER_AGENT])
+        .args(["--config", "-"])
+        .arg(&url)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .wrap_err("Could not run curl, is it installed?")?;
+    let config = format!(
+        "cookie = \"session={}\"\n",
+        session.replace('\\', "\\\\").replace('"', "\\\"")
+    );
+    let written = child
+        .stdin
+        .take()
+        .map(|mut stdin| stdin.write_all(config.as_bytes()));
+    let output = child
+        .wait_with_output()
+        .wrap_err("Could not wait for curl")?;
+    if let Some(Err(e)) = written {
+        return Err(e).wrap_err("Could not pass the session cookie to curl");
+    }
+
+    if !output.status.success() {
+        bail!(
+            "Could not fetch {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    if output.stdout.is_empty() {
+        bail!("Fetched an empty input from {}", url);
+    }
+
+    std::fs::write(path, &output.stdout)
+        .wrap_err_with(|| format!("Could not write input to {}", path.display()))?;
+
+    Ok(Fetched::Written(output.stdout.len()))
+}
+
+pub(crate) fn read_session(path: &str) -> eyre::Result<String> {
+    if let Ok(session) = std::env::var("AOC_SESSION") {
+        return Ok(session.trim().to_string());
+    }
+    let session = std::fs::read_to_string(path).wrap_err_with(|| {
+        eyre!(
+            "Could not read the session cookie from {}, or from AOC_SESSION",
+            path
+        )
+    })?;
+    Ok(session.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{BufRead, BufReader},
+        net::TcpListener,
+        thread,
+    };
+
+    fn serve(response: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            while reader.read_line(&mut request).unwrap() > 2 {}
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+        (base_url, server)
+    }
+
+    #[test]
+    fn test_fetch() {
+        let path = std::env::temp_dir().join(format!("aoc2021-fetch-{}.txt", std::process::id()));
+        let session = || Ok(String::from("s3cr\"t"));
+
+        let (base_url, server) =
+            serve("HTTP/1.1 200 OK\r\nContent-Length: 6\r\nConnection: close\r\n\r\n3,4,3\n");
+        assert!(matches!(
+            fetch(6, &path, &format!("{base_url}/"), session, false),
+            Ok(Fetched::Written(6))
+        ));
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2021/day/6/input HTTP/1.1\r\n"));
+        assert!(request.contains("\r\nCookie: session=s3cr\"t\r\n"));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "3,4,3\n");
+        assert!(matches!(
+            fetch(6, &path, &base_url, || unreachable!(), false),
+            Ok(Fetched::Cached)
+        ));
+
+        let (base_url, server) =
+            serve("HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+        assert!(fetch(6, &path, &base_url, session, true).is_err());
+        server.join().unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+}
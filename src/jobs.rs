@@ -0,0 +1,230 @@
+use std::{
+    any::Any,
+    cell::Cell,
+    fmt::{self, Display},
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        mpsc, Arc, Once,
+    },
+    thread,
+    time::Duration,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Failure {
+    Panicked(String),
+    TimedOut(Duration),
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Panicked(message) => f.write_str(message),
+            Self::TimedOut(timeout) => write!(
+                f,
+                "timed out after {}",
+                humantime::format_duration(*timeout)
+            ),
+        }
+    }
+}
+
+pub(crate) fn isolated<R>(
+    timeout: Option<Duration>,
+    activity: &Activity,
+    job: impl FnOnce() -> R + Send + 'static,
+) -> Result<R, Failure>
+where
+    R: Send + 'static,
+{
+    let Some(timeout) = timeout else {
+        return quietly(job).map_err(panic_message);
+    };
+
+    // A job that timed out can't be stopped, so it keeps running on its own thread, and counts as
+    // active until it finishes. Whichever of the job and the wait for it ends last sees
+    // `finished` set, so a job abandoned just as it finishes doesn't count as active forever.
+    let finished = Arc::new(AtomicBool::new(false));
+    let (tx, rx) = mpsc::channel();
+    let (abandoned, job_finished) = (Arc::clone(&activity.abandoned), Arc::clone(&finished));
+    thread::spawn(move || {
+        let _ = tx.send(quietly(job));
+        if job_finished.swap(true, Ordering::SeqCst) {
+            abandoned.fetch_sub(1, Ordering::SeqCst);
+        }
+    });
+    rx.recv_timeout(timeout).map_or_else(
+        |_| {
+            activity.abandoned.fetch_add(1, Ordering::SeqCst);
+            if finished.swap(true, Ordering::SeqCst) {
+                activity.abandoned.fetch_sub(1, Ordering::SeqCst);
+            }
+            Err(Failure::TimedOut(timeout))
+        },
+        |result| result.map_err(panic_message),
+    )
+}
+
+thread_local! {
+    static QUIET: Cell<bool> = Cell::new(false);
+}
+
+// The panic hook is shared by all threads, so rather than swapping it around each job, it is
+// replaced once by one that stays silent on threads that are running an isolated job.
+fn quietly<R>(job: impl FnOnce() -> R) -> thread::Result<R> {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(Cell::get) {
+                default(info);
+            }
+        }));
+    });
+
+    let was_quiet = QUIET.with(|quiet| quiet.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(job));
+    QUIET.with(|quiet| quiet.set(was_quiet));
+    result
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> Failure {
+    let message = match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload.downcast_ref::<&str>().map_or_else(
+            || String::from("panicked"),
+            |message| (*message).to_string(),
+        ),
+    };
+    Failure::Panicked(message)
+}
+
+#[derive(Default)]
+pub(crate) struct Activity {
+    active: AtomicUsize,
+    started: AtomicUsize,
+    abandoned: Arc<AtomicUsize>,
+}
+
+impl Activity {
+    fn track<R>(&self, job: impl FnOnce() -> R) -> (R, bool) {
+        let started = self.started.fetch_add(1, Ordering::SeqCst) + 1;
+        let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+        let abandoned = self.abandoned.load(Ordering::SeqCst);
+        let result = job();
+        let contended = active > 1
+            || abandoned > 0
+            || self.abandoned.load(Ordering::SeqCst) > 0
+            || self.active.load(Ordering::SeqCst) > 1
+            || self.started.load(Ordering::SeqCst) != started;
+        self.active.fetch_sub(1, Ordering::SeqCst);
+        (result, contended)
+    }
+}
+
+pub(crate) fn run_ordered<T, R>(
+    items: &[T],
+    jobs: usize,
+    activity: &Activity,
+    work: impl Fn(&T) -> R + Sync,
+    mut emit: impl FnMut(R, bool),
+) where
+    T: Sync,
+    R: Send,
+{
+    if jobs <= 1 {
+        for item in items {
+            let (result, contended) = activity.track(|| work(item));
+            emit(result, contended);
+        }
+        return;
+    }
+
+    let next = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(items.len()) {
+            let (tx, next, work) = (tx.clone(), &next, &work);
+            scope.spawn(move || loop {
+                let idx = next.fetch_add(1, Ordering::SeqCst);
+                let Some(item) = items.get(idx) else { break };
+                if tx.send((idx, activity.track(|| work(item)))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut done = items.iter().map(|_| None).collect::<Vec<_>>();
+        let mut emitted = 0;
+        for (idx, result) in rx {
+            done[idx] = Some(result);
+            while let Some((result, contended)) = done.get_mut(emitted).and_then(Option::take) {
+                emit(result, contended);
+                emitted += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_ordered() {
+        let items = (0..16_u64).collect::<Vec<_>>();
+        let mut emitted = Vec::new();
+        run_ordered(
+            &items,
+            4,
+            &Activity::default(),
+            |&item| {
+                thread::sleep(Duration::from_millis(16 - item));
+                item
+            },
+            |item, contended| emitted.push((item, contended)),
+        );
+        assert_eq!(
+            emitted.iter().map(|&(item, _)| item).collect::<Vec<_>>(),
+            items
+        );
+        assert!(emitted.iter().any(|&(_, contended)| contended));
+
+        let mut emitted = Vec::new();
+        run_ordered(
+            &items,
+            1,
+            &Activity::default(),
+            |&item| item,
+            |item, contended| emitted.push((item, contended)),
+        );
+        assert_eq!(
+            emitted,
+            items.iter().map(|&item| (item, false)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_isolated() {
+        let activity = Activity::default();
+        assert_eq!(isolated(None, &activity, || 42), Ok(42));
+
+        let failed = isolated(None, &activity, || -> u32 { panic!("day {} is broken", 2) });
+        let timeout = Duration::from_millis(10);
+        let timed_out = isolated(Some(timeout), &activity, || {
+            thread::sleep(Duration::from_millis(200));
+        });
+
+        assert_eq!(
+            failed,
+            Err(Failure::Panicked(String::from("day 2 is broken")))
+        );
+        assert_eq!(timed_out, Err(Failure::TimedOut(timeout)));
+        assert_eq!(activity.track(|| ()), ((), true));
+        thread::sleep(Duration::from_millis(400));
+        assert_eq!(activity.track(|| ()), ((), false));
+    }
+}
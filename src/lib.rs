@@ -1,4 +1,16 @@
-use std::{fmt::Debug, fmt::Display, marker::PhantomData, str::FromStr, time::Duration};
+pub use allocations::Allocations;
+use derive_more::Deref;
+use num::BigInt;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use std::{
+    borrow::Cow, collections::HashMap, convert::Infallible, fmt::Display, marker::PhantomData,
+    ops::Index, ops::IndexMut, str::FromStr, sync::Mutex, time::Duration, time::Instant,
+};
+pub use timing::{Spread, Timing};
+
+pub mod allocations;
+pub mod timing;
 
 pub trait MedianExt<T> {
     fn median(self) -> T;
@@ -19,13 +31,155 @@ impl<T: Ord + Copy> MedianExt<T> for Vec<T> {
     }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: Option<u8>,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+    offset: usize,
+}
+
+impl ParseError {
+    // Errors are located by the address of `span`, which has to be a slice of `input`.
+    pub fn new(input: &str, span: &str, message: impl Display) -> Self {
+        let mut error = Self {
+            day: None,
+            line: 0,
+            column: 0,
+            text: span.to_string(),
+            message: message.to_string(),
+            offset: offset_of(input, span),
+        };
+        error.locate(input);
+        error
+    }
+
+    #[must_use]
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        self.offset += offset_of(outer, inner);
+        self.locate(outer);
+        self
+    }
+
+    #[must_use]
+    pub fn for_day(self, day: u8) -> Self {
+        Self {
+            day: Some(day),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn unnormalized(mut self, raw: &str, normalized: &str) -> Self {
+        let raw = raw.strip_prefix('\u{feff}').unwrap_or(raw);
+        let before = &normalized[..self.offset.min(normalized.len())];
+        let line = before.matches('\n').count();
+        let column = before.len() - before.rfind('\n').map_or(0, |pos| pos + 1);
+        let indent = common_indent(raw);
+        if let Some(raw_line) = raw.lines().skip_while(|l| l.trim().is_empty()).nth(line) {
+            let trimmed = raw_line.trim_end();
+            let dropped = trimmed
+                .get(indent..)
+                .map_or(trimmed.len() - trimmed.trim_start().len(), |_| indent);
+            self.offset = offset_of(raw, raw_line) + dropped + column;
+        }
+        self.locate(raw);
+        self
+    }
+
+    fn locate(&mut self, input: &str) {
+        let before = &input[..self.offset.min(input.len())];
+        let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+        self.line = before.matches('\n').count() + 1;
+        self.column = before[line_start..].chars().count() + 1;
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {day}, ")?;
+        }
+        write!(
+            f,
+            "line {}, column {}: {} in {:?}",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn offset_of(outer: &str, inner: &str) -> usize {
+    let start = outer.as_ptr() as usize;
+    let pos = inner.as_ptr() as usize;
+    if (start..=start + outer.len()).contains(&pos) {
+        pos - start
+    } else {
+        0
+    }
+}
+
 pub trait PuzzleInput
 where
     Self: Sized,
 {
-    type Out;
+    type Out<'a>;
+
+    const NORMALIZE: bool = true;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError>;
+
+    fn from_input(input: &str) -> Self::Out<'_> {
+        match Self::try_from_input(input) {
+            Ok(out) => out,
+            Err(e) => panic!("{}", e),
+        }
+    }
+}
+
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let indent = common_indent(input);
+
+    if indent == 0 && !input.contains('\r') && input.lines().all(|l| l.trim_end() == l) {
+        return Cow::Borrowed(input.trim_start_matches('\n').trim_end());
+    }
+
+    let mut normalized = String::with_capacity(input.len());
+    for line in input.lines().skip_while(|line| line.trim().is_empty()) {
+        let line = line.trim_end();
+        normalized.push_str(line.get(indent..).unwrap_or_else(|| line.trim_start()));
+        normalized.push('\n');
+    }
+    normalized.truncate(normalized.trim_end().len());
+    Cow::Owned(normalized)
+}
+
+fn common_indent(input: &str) -> usize {
+    input
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default()
+}
+
+pub struct Raw<T>(PhantomData<T>);
+
+impl<T> PuzzleInput for Raw<T>
+where
+    T: PuzzleInput,
+{
+    type Out<'a> = T::Out<'a>;
 
-    fn from_input(input: &str) -> Self::Out;
+    const NORMALIZE: bool = false;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        T::try_from_input(input)
+    }
 }
 
 pub struct Blocks<T>(PhantomData<T>);
@@ -34,10 +188,61 @@ impl<T> PuzzleInput for Blocks<T>
 where
     T: PuzzleInput,
 {
-    type Out = Vec<T::Out>;
+    type Out<'a> = Vec<T::Out<'a>>;
+
+    const NORMALIZE: bool = T::NORMALIZE;
 
-    fn from_input(input: &str) -> Self::Out {
-        input.split("\n\n").map(|l| T::from_input(l)).collect()
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        input
+            .split("\n\n")
+            .map(|block| T::try_from_input(block).map_err(|e| e.within(input, block)))
+            .collect()
+    }
+}
+
+pub struct Sections<T>(PhantomData<T>);
+
+pub type Header<A, B> = Sections<(A, B)>;
+
+fn split_section(input: &str) -> Result<(&str, &str), ParseError> {
+    input
+        .split_once("\n\n")
+        .ok_or_else(|| ParseError::new(input, &input[input.len()..], "expected another section"))
+}
+
+impl<A, B> PuzzleInput for Sections<(A, B)>
+where
+    A: PuzzleInput,
+    B: PuzzleInput,
+{
+    type Out<'a> = (A::Out<'a>, B::Out<'a>);
+
+    const NORMALIZE: bool = A::NORMALIZE && B::NORMALIZE;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        let (a, b) = split_section(input)?;
+        Ok((
+            A::try_from_input(a).map_err(|e| e.within(input, a))?,
+            B::try_from_input(b).map_err(|e| e.within(input, b))?,
+        ))
+    }
+}
+
+impl<A, B, C> PuzzleInput for Sections<(A, B, C)>
+where
+    A: PuzzleInput,
+    B: PuzzleInput,
+    C: PuzzleInput,
+{
+    type Out<'a> = (A::Out<'a>, B::Out<'a>, C::Out<'a>);
+
+    const NORMALIZE: bool = A::NORMALIZE && B::NORMALIZE && C::NORMALIZE;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        let (a, rest) = split_section(input)?;
+        let a = A::try_from_input(a).map_err(|e| e.within(input, a))?;
+        let (b, c) = Sections::<(B, C)>::try_from_input(rest).map_err(|e| e.within(input, rest))?;
+        Ok((a, b, c))
     }
 }
 
@@ -46,25 +251,328 @@ pub struct Parsing<T>(PhantomData<T>);
 impl<T> PuzzleInput for Parsing<T>
 where
     T: FromStr,
-    <T as FromStr>::Err: Debug,
+    <T as FromStr>::Err: Display,
 {
-    type Out = Vec<T>;
+    type Out<'a> = Vec<T>;
 
-    fn from_input(input: &str) -> Self::Out {
-        lines(input).map(|l| T::from_str(l).unwrap()).collect()
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        lines(input)
+            .map(|l| T::from_str(l).map_err(|e| ParseError::new(input, l, e)))
+            .collect()
     }
 }
 
 pub struct As<T>(PhantomData<T>);
 
-impl<T> PuzzleInput for As<T>
+impl<T, E> PuzzleInput for As<T>
+where
+    T: for<'s> TryFrom<&'s str, Error = E>,
+    E: Display,
+{
+    type Out<'a> = Vec<T>;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        lines(input)
+            .map(|l| T::try_from(l).map_err(|e| ParseError::new(input, l, e)))
+            .collect()
+    }
+}
+
+pub struct Lines;
+
+impl PuzzleInput for Lines {
+    type Out<'a> = Vec<&'a str>;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        Ok(lines(input).collect())
+    }
+}
+
+pub struct Separated<T, const SEP: char>(PhantomData<T>);
+
+pub type Csv<T> = Separated<T, ','>;
+
+impl<T, const SEP: char> PuzzleInput for Separated<T, SEP>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Display,
+{
+    type Out<'a> = Vec<T>;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        lines(input)
+            .flat_map(|l| l.split(SEP))
+            .map(str::trim)
+            .map(|f| T::from_str(f).map_err(|e| ParseError::new(input, f, e)))
+            .collect()
+    }
+}
+
+pub trait Cell: Sized {
+    type Err: Display;
+
+    fn from_char(c: char) -> Result<Self, Self::Err>;
+}
+
+impl Cell for u8 {
+    type Err = &'static str;
+
+    fn from_char(c: char) -> Result<Self, Self::Err> {
+        c.to_digit(10)
+            .and_then(|d| Self::try_from(d).ok())
+            .ok_or("expected a digit")
+    }
+}
+
+impl Cell for char {
+    type Err = Infallible;
+
+    fn from_char(c: char) -> Result<Self, Self::Err> {
+        Ok(c)
+    }
+}
+
+impl Cell for bool {
+    type Err = &'static str;
+
+    fn from_char(c: char) -> Result<Self, Self::Err> {
+        match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("expected '#' or '.'"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        (row < self.height && col < self.width).then(|| &self.cells[row * self.width + col])
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        (row < self.height && col < self.width).then(|| &mut self.cells[row * self.width + col])
+    }
+
+    pub const fn position(&self, index: usize) -> (usize, usize) {
+        (index / self.width, index % self.width)
+    }
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<usize> for Grid<T> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.cells[index]
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        assert!(col < self.width, "column {col} out of bounds");
+        &self.cells[row * self.width + col]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        assert!(col < self.width, "column {col} out of bounds");
+        &mut self.cells[row * self.width + col]
+    }
+}
+
+impl<T> PuzzleInput for Grid<T>
 where
-    T: From<String>,
+    T: Cell,
 {
-    type Out = Vec<T>;
+    type Out<'a> = Self;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        let mut grid = Self {
+            cells: Vec::with_capacity(input.len()),
+            width: 0,
+            height: 0,
+        };
+        for row in lines(input) {
+            let width = row.chars().count();
+            if grid.height == 0 {
+                grid.width = width;
+            } else if width != grid.width {
+                let message = format!("expected a row of width {}, got {}", grid.width, width);
+                return Err(ParseError::new(input, row, message));
+            }
+            for (pos, c) in row.char_indices() {
+                let cell = T::from_char(c)
+                    .map_err(|e| ParseError::new(input, &row[pos..pos + c.len_utf8()], e))?;
+                grid.cells.push(cell);
+            }
+            grid.height += 1;
+        }
+        Ok(grid)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ByteGrid<'a> {
+    bytes: &'a [u8],
+    width: usize,
+    height: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn row(&self, row: usize) -> &'a [u8] {
+        let start = row * (self.width + 1);
+        &self.bytes[start..start + self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.height).map(|row| self.row(row))
+    }
 
-    fn from_input(input: &str) -> Self::Out {
-        lines(input).map(|l| T::from(String::from(l))).collect()
+    pub fn get(&self, row: usize, col: usize) -> Option<u8> {
+        (row < self.height && col < self.width).then(|| self[(row, col)])
+    }
+}
+
+impl Index<(usize, usize)> for ByteGrid<'_> {
+    type Output = u8;
+
+    fn index(&self, (row, col): (usize, usize)) -> &u8 {
+        assert!(col < self.width, "column {col} out of bounds");
+        &self.bytes[row * (self.width + 1) + col]
+    }
+}
+
+impl PuzzleInput for ByteGrid<'_> {
+    type Out<'a> = ByteGrid<'a>;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        let rows = input.strip_suffix('\n').unwrap_or(input);
+        let width = rows.find('\n').unwrap_or(rows.len());
+        let mut height = 0;
+        for row in rows.split('\n').filter(|_| !rows.is_empty()) {
+            if row.len() != width {
+                let message = format!("expected a row of width {}, got {}", width, row.len());
+                return Err(ParseError::new(input, row, message));
+            }
+            height += 1;
+        }
+        Ok(ByteGrid {
+            bytes: rows.as_bytes(),
+            width,
+            height,
+        })
+    }
+}
+
+pub trait FromCaptures: Sized {
+    const PATTERN: &'static str;
+
+    fn from_captures(captures: &Fields<'_>) -> Result<Self, ParseError>;
+}
+
+pub struct Fields<'a> {
+    input: &'a str,
+    captures: Captures<'a>,
+}
+
+impl<'a> Fields<'a> {
+    pub fn str(&self, name: &str) -> Option<&'a str> {
+        self.captures.name(name).map(|m| m.as_str())
+    }
+
+    pub fn parse<T>(&self, name: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        <T as FromStr>::Err: Display,
+    {
+        let Some(field) = self.str(name) else {
+            let line = self.captures.get(0).map_or("", |m| m.as_str());
+            return Err(ParseError::new(
+                self.input,
+                line,
+                format!("missing capture {name}"),
+            ));
+        };
+        T::from_str(field)
+            .map_err(|e| ParseError::new(self.input, field, format!("invalid {name}: {e}")))
+    }
+}
+
+fn cached_regex(pattern: &'static str) -> &'static Regex {
+    static CACHE: Lazy<Mutex<HashMap<&'static str, &'static Regex>>> = Lazy::new(Mutex::default);
+
+    let mut cache = CACHE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    cache.entry(pattern).or_insert_with(|| {
+        let regex = Regex::new(&format!("^(?:{pattern})$"))
+            .unwrap_or_else(|e| panic!("invalid pattern {pattern:?}: {e}"));
+        Box::leak(Box::new(regex))
+    })
+}
+
+pub struct Matching<T>(PhantomData<T>);
+
+impl<T> PuzzleInput for Matching<T>
+where
+    T: FromCaptures,
+{
+    type Out<'a> = Vec<T>;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        let regex = cached_regex(T::PATTERN);
+        lines(input)
+            .map(|line| {
+                let captures = regex.captures(line).ok_or_else(|| {
+                    let message = format!("expected a line matching {:?}", T::PATTERN);
+                    ParseError::new(input, line, message)
+                })?;
+                T::from_captures(&Fields { input, captures })
+            })
+            .collect()
     }
 }
 
@@ -72,95 +580,532 @@ pub fn lines(s: &str) -> impl Iterator<Item = &str> + '_ {
     s.lines().map(str::trim).filter(|line| !line.is_empty())
 }
 
-pub struct PuzzleSolution<T> {
-    pub part1: T,
-    pub part2: T,
+pub struct PuzzleSolution<T1, T2 = T1> {
+    pub part1: T1,
+    pub part2: T2,
     pub timings: Option<(Duration, Duration)>,
+    pub spread: Option<(Spread, Spread)>,
+    pub parse_time: Option<Duration>,
+    pub allocations: Option<(Allocations, Allocations)>,
+    pub parse_allocations: Option<Allocations>,
+}
+
+impl<T1, T2> PuzzleSolution<T1, T2> {
+    pub fn total_time(&self) -> Option<Duration> {
+        let (part1, part2) = self.timings?;
+        Some(self.parse_time? + part1 + part2)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::One => f.write_str("1"),
+            Self::Two => f.write_str("2"),
+        }
+    }
+}
+
+// Integers are normalized, so that the same number compares equal whichever type it came from.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u128),
+    Signed(i128),
+    Big(BigInt),
+    Text(String),
+}
+
+macro_rules! answer_from_unsigned {
+    ($($ty:ty),+) => {
+        $(impl From<$ty> for Answer {
+            fn from(n: $ty) -> Self {
+                Self::Unsigned(n as u128)
+            }
+        })+
+    };
+}
+
+macro_rules! answer_from_signed {
+    ($($ty:ty),+) => {
+        $(impl From<$ty> for Answer {
+            fn from(n: $ty) -> Self {
+                let n = n as i128;
+                u128::try_from(n).map_or(Self::Signed(n), Self::Unsigned)
+            }
+        })+
+    };
+}
+
+answer_from_unsigned!(u8, u16, u32, u64, u128, usize);
+answer_from_signed!(i8, i16, i32, i64, i128, isize);
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        if let Ok(n) = u128::try_from(&n) {
+            Self::Unsigned(n)
+        } else if let Ok(n) = i128::try_from(&n) {
+            Self::Signed(n)
+        } else {
+            Self::Big(n)
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Self::Text(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Self::Text(s.to_string())
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.parse::<BigInt>() {
+            Ok(n) if n.to_string() == s => Self::from(n),
+            _ => Self::from(s),
+        })
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unsigned(n) => n.fmt(f),
+            Self::Signed(n) => n.fmt(f),
+            Self::Big(n) => n.fmt(f),
+            Self::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+pub struct PartSolution<T> {
+    pub part: Part,
+    pub answer: T,
+    pub time: Option<Duration>,
+    pub spread: Option<Spread>,
+    pub parse_time: Option<Duration>,
+    pub allocations: Option<Allocations>,
+    pub parse_allocations: Option<Allocations>,
+}
+
+impl<T> PartSolution<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> PartSolution<U> {
+        PartSolution {
+            part: self.part,
+            answer: f(self.answer),
+            time: self.time,
+            spread: self.spread,
+            parse_time: self.parse_time,
+            allocations: self.allocations,
+            parse_allocations: self.parse_allocations,
+        }
+    }
+
+    pub fn total_time(&self) -> Option<Duration> {
+        Some(self.parse_time? + self.time?)
+    }
 }
 
 pub trait Solution {
     type Input: PuzzleInput;
-    type Output;
+    type Output1;
+    type Output2;
+
+    const DAY: u8;
+    const TITLE: &'static str;
+    const INPUT_PATH: &'static str;
+    const IMPLEMENTED: bool;
+    const EXAMPLES: &'static [&'static str] = &[];
 
     fn puzzle_input() -> &'static str;
 
-    fn run(input: <Self::Input as PuzzleInput>::Out) -> (Self::Output, Self::Output) {
-        let PuzzleSolution {
-            part1,
-            part2,
-            timings: _,
-        } = Self::timed_run(input);
+    fn run(input: <Self::Input as PuzzleInput>::Out<'_>) -> (Self::Output1, Self::Output2) {
+        let PuzzleSolution { part1, part2, .. } = Self::timed_run(input, Timing::Once);
         (part1, part2)
     }
 
-    fn timed_run(input: <Self::Input as PuzzleInput>::Out) -> PuzzleSolution<Self::Output>;
+    fn adaptive() -> bool {
+        false
+    }
+
+    fn timed_run(
+        input: <Self::Input as PuzzleInput>::Out<'_>,
+        timing: Timing,
+    ) -> PuzzleSolution<Self::Output1, Self::Output2>;
+
+    fn part1(input: <Self::Input as PuzzleInput>::Out<'_>) -> Self::Output1;
+
+    fn part2(input: <Self::Input as PuzzleInput>::Out<'_>) -> Self::Output2;
+
+    fn timed_part1(
+        input: <Self::Input as PuzzleInput>::Out<'_>,
+        _timing: Timing,
+    ) -> PartSolution<Self::Output1> {
+        let start = Instant::now();
+        let (answer, allocations) = allocations::measure(|| Self::part1(input));
+        PartSolution {
+            part: Part::One,
+            answer,
+            time: Some(start.elapsed()),
+            spread: None,
+            parse_time: None,
+            allocations,
+            parse_allocations: None,
+        }
+    }
+
+    fn timed_part2(
+        input: <Self::Input as PuzzleInput>::Out<'_>,
+        _timing: Timing,
+    ) -> PartSolution<Self::Output2> {
+        let start = Instant::now();
+        let (answer, allocations) = allocations::measure(|| Self::part2(input));
+        PartSolution {
+            part: Part::Two,
+            answer,
+            time: Some(start.elapsed()),
+            spread: None,
+            parse_time: None,
+            allocations,
+            parse_allocations: None,
+        }
+    }
+
+    #[inline]
+    fn prepare_input(input: &str) -> Cow<'_, str> {
+        if <Self::Input as PuzzleInput>::NORMALIZE {
+            normalize(input)
+        } else {
+            Cow::Borrowed(input)
+        }
+    }
+
+    #[inline]
+    fn parse_input<'a>(raw: &str, input: &'a str) -> <Self::Input as PuzzleInput>::Out<'a> {
+        match Self::try_parse_input(raw, input) {
+            Ok(input) => input,
+            Err(e) => panic!("{}", e),
+        }
+    }
 
     #[inline]
-    fn parse_input(input: &str) -> <Self::Input as PuzzleInput>::Out {
-        <Self::Input as PuzzleInput>::from_input(input)
+    fn try_parse_input<'a>(
+        raw: &str,
+        input: &'a str,
+    ) -> Result<<Self::Input as PuzzleInput>::Out<'a>, ParseError> {
+        <Self::Input as PuzzleInput>::try_from_input(input).map_err(|e| {
+            if <Self::Input as PuzzleInput>::NORMALIZE {
+                e.unnormalized(raw, input).for_day(Self::DAY)
+            } else {
+                e.for_day(Self::DAY)
+            }
+        })
     }
 
     #[inline]
-    fn run_on(input: &str) -> (Self::Output, Self::Output) {
-        let input = Self::parse_input(input);
-        Self::run(input)
+    fn run_on(input: &str) -> (Self::Output1, Self::Output2) {
+        let prepared = Self::prepare_input(input);
+        Self::run(Self::parse_input(input, &prepared))
     }
 
     #[inline]
-    fn run_on_input() -> (Self::Output, Self::Output) {
+    fn run_on_input() -> (Self::Output1, Self::Output2) {
         let input = Self::puzzle_input();
         Self::run_on(input)
     }
 
     #[inline]
-    fn timed_run_on_input() -> PuzzleSolution<Self::Output> {
+    fn part1_on(input: &str) -> Self::Output1 {
+        let prepared = Self::prepare_input(input);
+        Self::part1(Self::parse_input(input, &prepared))
+    }
+
+    #[inline]
+    fn part2_on(input: &str) -> Self::Output2 {
+        let prepared = Self::prepare_input(input);
+        Self::part2(Self::parse_input(input, &prepared))
+    }
+
+    #[inline]
+    fn try_run_on(input: &str) -> Result<(Self::Output1, Self::Output2), ParseError> {
+        let prepared = Self::prepare_input(input);
+        let input = Self::try_parse_input(input, &prepared)?;
+        Ok(Self::run(input))
+    }
+
+    #[inline]
+    fn timed_run_on(
+        input: &str,
+        timing: Timing,
+    ) -> Result<PuzzleSolution<Self::Output1, Self::Output2>, ParseError> {
+        let start = Instant::now();
+        let measurement = allocations::Measurement::start();
+        let prepared = Self::prepare_input(input);
+        let input = Self::try_parse_input(input, &prepared)?;
+        let parse_allocations = measurement.finish();
+        let parse_time = start.elapsed();
+        Ok(PuzzleSolution {
+            parse_time: Some(parse_time),
+            parse_allocations,
+            ..Self::timed_run(input, timing)
+        })
+    }
+
+    #[inline]
+    fn timed_run_on_input(
+        timing: Timing,
+    ) -> Result<PuzzleSolution<Self::Output1, Self::Output2>, ParseError> {
         let input = Self::puzzle_input();
-        let input = Self::parse_input(input);
-        Self::timed_run(input)
+        Self::timed_run_on(input, timing)
     }
 
     #[inline]
-    fn solve() -> PuzzleSolution<Box<dyn Display>>
+    fn solve_on(input: &str, timing: Timing) -> Result<PuzzleSolution<Answer>, ParseError>
     where
-        Self::Output: Display + 'static,
+        Self::Output1: Into<Answer>,
+        Self::Output2: Into<Answer>,
     {
         let PuzzleSolution {
             part1,
             part2,
             timings,
-        } = Self::timed_run_on_input();
-        PuzzleSolution {
-            part1: Box::new(part1),
-            part2: Box::new(part2),
+            spread,
+            parse_time,
+            allocations,
+            parse_allocations,
+        } = Self::timed_run_on(input, timing)?;
+        Ok(PuzzleSolution {
+            part1: part1.into(),
+            part2: part2.into(),
             timings,
+            spread,
+            parse_time,
+            allocations,
+            parse_allocations,
+        })
+    }
+
+    #[inline]
+    fn solve_part_on(
+        part: Part,
+        input: &str,
+        timing: Timing,
+    ) -> Result<PartSolution<Answer>, ParseError>
+    where
+        Self::Output1: Into<Answer>,
+        Self::Output2: Into<Answer>,
+    {
+        let start = Instant::now();
+        let measurement = allocations::Measurement::start();
+        let prepared = Self::prepare_input(input);
+        let input = Self::try_parse_input(input, &prepared)?;
+        let parse_allocations = measurement.finish();
+        let parse_time = start.elapsed();
+        let solution = match part {
+            Part::One => Self::timed_part1(input, timing).map(Into::into),
+            Part::Two => Self::timed_part2(input, timing).map(Into::into),
+        };
+        Ok(PartSolution {
+            parse_time: Some(parse_time),
+            parse_allocations,
+            ..solution
+        })
+    }
+
+    #[inline]
+    fn solve(timing: Timing) -> Result<PuzzleSolution<Answer>, ParseError>
+    where
+        Self::Output1: Into<Answer>,
+        Self::Output2: Into<Answer>,
+    {
+        Self::solve_on(Self::puzzle_input(), timing)
+    }
+}
+
+type Solve = fn(&str, Timing) -> Result<PuzzleSolution<Answer>, ParseError>;
+type SolvePart = fn(Part, &str, Timing) -> Result<PartSolution<Answer>, ParseError>;
+
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u8,
+    pub title: &'static str,
+    pub input_path: &'static str,
+    pub implemented: bool,
+    pub adaptive: bool,
+    pub examples: &'static [&'static str],
+    puzzle_input: fn() -> &'static str,
+    solve_on: Solve,
+    solve_part_on: SolvePart,
+}
+
+impl Day {
+    pub fn of<S>() -> Self
+    where
+        S: Solution,
+        S::Output1: Into<Answer>,
+        S::Output2: Into<Answer>,
+    {
+        Self {
+            day: S::DAY,
+            title: S::TITLE,
+            input_path: S::INPUT_PATH,
+            implemented: S::IMPLEMENTED,
+            adaptive: S::adaptive(),
+            examples: S::EXAMPLES,
+            puzzle_input: S::puzzle_input,
+            solve_on: S::solve_on,
+            solve_part_on: S::solve_part_on,
         }
     }
+
+    pub fn puzzle_input(&self) -> &'static str {
+        (self.puzzle_input)()
+    }
+
+    pub fn solve_on(
+        &self,
+        input: &str,
+        timing: Timing,
+    ) -> Result<PuzzleSolution<Answer>, ParseError> {
+        (self.solve_on)(input, timing)
+    }
+
+    pub fn solve(&self, timing: Timing) -> Result<PuzzleSolution<Answer>, ParseError> {
+        self.solve_on(self.puzzle_input(), timing)
+    }
+
+    pub fn solve_part_on(
+        &self,
+        part: Part,
+        input: &str,
+        timing: Timing,
+    ) -> Result<PartSolution<Answer>, ParseError> {
+        (self.solve_part_on)(part, input, timing)
+    }
+
+    pub fn solve_part(
+        &self,
+        part: Part,
+        timing: Timing,
+    ) -> Result<PartSolution<Answer>, ParseError> {
+        self.solve_part_on(part, self.puzzle_input(), timing)
+    }
+}
+
+#[derive(Deref)]
+pub struct Registry(Vec<Day>);
+
+impl Registry {
+    pub fn new(mut days: Vec<Day>) -> Self {
+        days.sort_by_key(|d| d.day);
+        Self(days)
+    }
+
+    pub fn get(&self, day: u8) -> Option<&Day> {
+        self.0
+            .binary_search_by_key(&day, |d| d.day)
+            .ok()
+            .map(|idx| &self.0[idx])
+    }
 }
 
 #[macro_export]
 macro_rules! register {
 
-    ($file:literal; run($input:ident: $input_ty:ty) -> $output_ty:ty $runner:block) => {
+    ($day:literal, $title:literal, $file:literal; pending $($solver:tt)+) => {
         #[rustfmt::skip]
-        register!($file; run($input: verbatim $crate::As<$input_ty>) -> $output_ty $runner);
+        register!(@[$day, $title, $file, false] $($solver)+);
     };
 
-    ($file:literal; run($input:ident: parse $input_ty:ty) -> $output_ty:ty $runner:block) => {
+    ($day:literal, $title:literal, $file:literal; $($solver:tt)+) => {
         #[rustfmt::skip]
-        register!($file; run($input: verbatim $crate::Parsing<$input_ty>) -> $output_ty $runner);
+        register!(@[$day, $title, $file, true] $($solver)+);
+    };
+
+    (@repeat $input_ty:ty) => {
+        (&$crate::timing::Repeat::<<$input_ty as $crate::PuzzleInput>::Out<'_>>::new())
+    };
+
+    (@shape verbatim $input_ty:ty) => { $input_ty };
+    (@shape parse $input_ty:ty) => { $crate::Parsing<$input_ty> };
+    (@shape csv $input_ty:ty) => { $crate::Csv<$input_ty> };
+    (@shape grid $input_ty:ty) => { $crate::Grid<$input_ty> };
+    (@shape bytes) => { $crate::ByteGrid<'static> };
+    (@shape lines) => { $crate::Lines };
+    (@shape matching $input_ty:ty) => { $crate::Matching<$input_ty> };
+    (@shape chunk $($shape:tt)+) => { $crate::Blocks<register!(@shape $($shape)+)> };
+    (@shape $input_ty:ty) => { $crate::As<$input_ty> };
+
+    (@examples $(examples: [$(($example:expr, $expected1:expr, $expected2:expr $(,)?)),* $(,)?] $(;)?)? $(answers: ($answer1:expr, $answer2:expr) $(;)?)?) => {
+        &[$($($example),*)?]
+    };
+
+    (@tests false $($rest:tt)*) => {};
+
+    (@tests true $(examples: [$(($example:expr, $expected1:expr, $expected2:expr $(,)?)),* $(,)?] $(;)?)? $(answers: ($answer1:expr, $answer2:expr) $(;)?)?) => {
+        #[cfg(test)]
+        mod register_tests {
+            $(
+                #[test]
+                fn test_examples() {
+                    use super::Solver;
+                    use $crate::Solution as _;
+
+                    let mut example = 0;
+                    $(
+                        example += 1;
+                        let (part1, part2) = Solver::run_on($example);
+                        assert_eq!(part1, $expected1, "part 1 of example {}", example);
+                        assert_eq!(part2, $expected2, "part 2 of example {}", example);
+                    )*
+                }
+            )?
+
+            $(
+                #[test]
+                fn test_answers() {
+                    use super::Solver;
+                    use $crate::Solution as _;
+
+                    let (part1, part2) = Solver::run_on_input();
+                    assert_eq!(part1, $answer1, "part 1");
+                    assert_eq!(part2, $answer2, "part 2");
+                }
+            )?
+        }
     };
 
-    ($file:literal; run($input:ident: chunk $input_ty:ty) -> $output_ty:ty $runner:block) => {
+    (@[$($head:tt)*] run($input:ident: verbatim $input_ty:ty) -> $output_ty:ty $runner:block $($rest:tt)*) => {
         #[rustfmt::skip]
-        register!($file; run($input: verbatim $crate::Blocks<$crate::As<$input_ty>>) -> $output_ty $runner);
+        register!(@[$($head)*] run($input: verbatim $input_ty) -> $output_ty, $output_ty $runner $($rest)*);
     };
 
-    ($file:literal; run($input:ident: verbatim $input_ty:ty) -> $output_ty:ty $runner:block) => {
+    (@[$day:literal, $title:literal, $file:literal, $implemented:tt] run($input:ident: verbatim $input_ty:ty) -> $output1_ty:ty, $output2_ty:ty $runner:block $($rest:tt)*) => {
         pub(crate) struct Solver;
 
         impl $crate::Solution for Solver {
             type Input = $input_ty;
-            type Output = $output_ty;
+            type Output1 = $output1_ty;
+            type Output2 = $output2_ty;
+
+            const DAY: u8 = $day;
+            const TITLE: &'static str = $title;
+            const INPUT_PATH: &'static str = ::std::concat!("src/", $file);
+            const IMPLEMENTED: bool = $implemented;
+            const EXAMPLES: &'static [&'static str] = register!(@examples $($rest)*);
 
             #[inline]
             fn puzzle_input() -> &'static str {
@@ -168,36 +1113,65 @@ macro_rules! register {
             }
 
             #[inline]
-            fn timed_run(mut $input: <$input_ty as $crate::PuzzleInput>::Out) -> $crate::PuzzleSolution<Self::Output> {
+            fn timed_run(mut $input: <$input_ty as $crate::PuzzleInput>::Out<'_>, _: $crate::Timing) -> $crate::PuzzleSolution<Self::Output1, Self::Output2> {
                 let (part1, part2) = $runner;
                 $crate::PuzzleSolution {
-                    part1, part2, timings: None
+                    part1, part2, timings: None, spread: None, parse_time: None, allocations: None, parse_allocations: None
+                }
+            }
+
+            // The runner computes both parts at once, so a single part is never faster than both.
+            #[inline]
+            fn part1(mut $input: <$input_ty as $crate::PuzzleInput>::Out<'_>) -> Self::Output1 {
+                $runner.0
+            }
+
+            #[inline]
+            fn part2(mut $input: <$input_ty as $crate::PuzzleInput>::Out<'_>) -> Self::Output2 {
+                $runner.1
+            }
+
+            #[inline]
+            fn timed_part1(input: <$input_ty as $crate::PuzzleInput>::Out<'_>, _: $crate::Timing) -> $crate::PartSolution<Self::Output1> {
+                $crate::PartSolution {
+                    part: $crate::Part::One, answer: Self::part1(input), time: None, spread: None, parse_time: None, allocations: None, parse_allocations: None
+                }
+            }
+
+            #[inline]
+            fn timed_part2(input: <$input_ty as $crate::PuzzleInput>::Out<'_>, _: $crate::Timing) -> $crate::PartSolution<Self::Output2> {
+                $crate::PartSolution {
+                    part: $crate::Part::Two, answer: Self::part2(input), time: None, spread: None, parse_time: None, allocations: None, parse_allocations: None
                 }
             }
         }
-    };
 
-    ($file:literal; ($input:ident: $input_ty:ty) -> $output_ty:ty { $part1:expr; $part2:expr $(;)? }) => {
-        #[rustfmt::skip]
-        register!($file; ($input: verbatim $crate::As<$input_ty>) -> $output_ty { $part1; $part2 });
+        register!(@tests $implemented $($rest)*);
     };
 
-    ($file:literal; ($input:ident: parse $input_ty:ty) -> $output_ty:ty { $part1:expr; $part2:expr $(;)? }) => {
+    (@[$($head:tt)*] run($input:ident: $($shape:tt)+) -> $($output_ty:ty),+ $runner:block $($rest:tt)*) => {
         #[rustfmt::skip]
-        register!($file; ($input: verbatim $crate::Parsing<$input_ty>) -> $output_ty { $part1; $part2 });
+        register!(@[$($head)*] run($input: verbatim register!(@shape $($shape)+)) -> $($output_ty),+ $runner $($rest)*);
     };
 
-    ($file:literal; ($input:ident: chunk $input_ty:ty) -> $output_ty:ty { $part1:expr; $part2:expr $(;)? }) => {
+    (@[$($head:tt)*] ($input:ident: verbatim $input_ty:ty) -> $output_ty:ty { $part1:expr; $part2:expr $(;)? } $($rest:tt)*) => {
         #[rustfmt::skip]
-        register!($file; ($input: verbatim $crate::Blocks<$crate::As<$input_ty>>) -> $output_ty { $part1; $part2 });
+        register!(@[$($head)*] ($input: verbatim $input_ty) -> $output_ty, $output_ty { $part1; $part2 } $($rest)*);
     };
 
-    ($file:literal; ($input:ident: verbatim $input_ty:ty) -> $output_ty:ty { $part1:expr; $part2:expr $(;)? }) => {
+    (@[$day:literal, $title:literal, $file:literal, $implemented:tt] ($input:ident: verbatim $input_ty:ty) -> $output1_ty:ty, $output2_ty:ty { $part1:expr; $part2:expr $(;)? } $($rest:tt)*) => {
         pub(crate) struct Solver;
 
         impl $crate::Solution for Solver {
             type Input = $input_ty;
-            type Output = $output_ty;
+            type Output1 = $output1_ty;
+            type Output2 = $output2_ty;
+
+            const DAY: u8 = $day;
+            const TITLE: &'static str = $title;
+            const INPUT_PATH: &'static str = ::std::concat!("src/", $file);
+            const IMPLEMENTED: bool = $implemented;
+            const EXAMPLES: &'static [&'static str] = register!(@examples $($rest)*);
 
             #[inline]
             fn puzzle_input() -> &'static str {
@@ -205,18 +1179,386 @@ macro_rules! register {
             }
 
             #[inline]
-            fn timed_run(mut $input: <$input_ty as $crate::PuzzleInput>::Out) -> $crate::PuzzleSolution<Self::Output> {
+            fn adaptive() -> bool {
+                #[allow(unused_imports)]
+                use $crate::timing::{Cloned as _, Uncloned as _};
+                register!(@repeat $input_ty).adaptive()
+            }
+
+            #[inline]
+            fn timed_run(mut $input: <$input_ty as $crate::PuzzleInput>::Out<'_>, timing: $crate::Timing) -> $crate::PuzzleSolution<Self::Output1, Self::Output2> {
+                #[allow(unused_imports)]
+                use $crate::timing::{Cloned as _, Uncloned as _};
+                // Adaptive timing repeats a part on clones of the input, before the part consumes it.
+                let repeated1 = register!(@repeat $input_ty).repeat(timing, &$input, |mut $input| -> Self::Output1 { $part1 });
                 let start = ::std::time::Instant::now();
-                let part1 = $part1;
-                let part1_time = start.elapsed();
+                let (part1, part1_allocations) = $crate::allocations::measure(|| $part1);
+                let part1_time = repeated1.map_or(start.elapsed(), |(time, _)| time);
+                let repeated2 = register!(@repeat $input_ty).repeat(timing, &$input, |mut $input| -> Self::Output2 { $part2 });
                 let start = ::std::time::Instant::now();
-                let part2 = $part2;
-                let part2_time = start.elapsed();
+                let (part2, part2_allocations) = $crate::allocations::measure(|| $part2);
+                let part2_time = repeated2.map_or(start.elapsed(), |(time, _)| time);
 
                 $crate::PuzzleSolution {
-                    part1, part2, timings: Some((part1_time, part2_time))
+                    part1,
+                    part2,
+                    timings: Some((part1_time, part2_time)),
+                    spread: repeated1.zip(repeated2).map(|((_, spread1), (_, spread2))| (spread1, spread2)),
+                    parse_time: None,
+                    allocations: part1_allocations.zip(part2_allocations),
+                    parse_allocations: None,
+                }
+            }
+
+            #[inline]
+            fn part1(mut $input: <$input_ty as $crate::PuzzleInput>::Out<'_>) -> Self::Output1 {
+                $part1
+            }
+
+            #[inline]
+            fn part2(mut $input: <$input_ty as $crate::PuzzleInput>::Out<'_>) -> Self::Output2 {
+                $part2
+            }
+
+            #[inline]
+            fn timed_part1(mut $input: <$input_ty as $crate::PuzzleInput>::Out<'_>, timing: $crate::Timing) -> $crate::PartSolution<Self::Output1> {
+                #[allow(unused_imports)]
+                use $crate::timing::{Cloned as _, Uncloned as _};
+                let repeated = register!(@repeat $input_ty).repeat(timing, &$input, |mut $input| -> Self::Output1 { $part1 });
+                let start = ::std::time::Instant::now();
+                let (answer, allocations) = $crate::allocations::measure(|| $part1);
+                $crate::PartSolution {
+                    part: $crate::Part::One,
+                    answer,
+                    time: Some(repeated.map_or(start.elapsed(), |(time, _)| time)),
+                    spread: repeated.map(|(_, spread)| spread),
+                    parse_time: None,
+                    allocations,
+                    parse_allocations: None,
                 }
             }
+
+            #[inline]
+            fn timed_part2(mut $input: <$input_ty as $crate::PuzzleInput>::Out<'_>, timing: $crate::Timing) -> $crate::PartSolution<Self::Output2> {
+                #[allow(unused_imports)]
+                use $crate::timing::{Cloned as _, Uncloned as _};
+                let repeated = register!(@repeat $input_ty).repeat(timing, &$input, |mut $input| -> Self::Output2 { $part2 });
+                let start = ::std::time::Instant::now();
+                let (answer, allocations) = $crate::allocations::measure(|| $part2);
+                $crate::PartSolution {
+                    part: $crate::Part::Two,
+                    answer,
+                    time: Some(repeated.map_or(start.elapsed(), |(time, _)| time)),
+                    spread: repeated.map(|(_, spread)| spread),
+                    parse_time: None,
+                    allocations,
+                    parse_allocations: None,
+                }
+            }
+        }
+
+        register!(@tests $implemented $($rest)*);
+    };
+
+    (@[$($head:tt)*] ($input:ident: $($shape:tt)+) -> $($output_ty:ty),+ { $part1:expr; $part2:expr $(;)? } $($rest:tt)*) => {
+        #[rustfmt::skip]
+        register!(@[$($head)*] ($input: verbatim register!(@shape $($shape)+)) -> $($output_ty),+ { $part1; $part2 } $($rest)*);
+    };
+}
+
+#[macro_export]
+macro_rules! registry {
+    ($($module:ident),+ $(,)?) => {
+        $(mod $module;)+
+
+        fn registry() -> $crate::Registry {
+            $crate::Registry::new(::std::vec![$($crate::Day::of::<$module::Solver>()),+])
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_location() {
+        let input = "1\n2\n  x3\n4";
+        let err = Parsing::<u32>::try_from_input(input).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(err.text, "x3");
+        assert_eq!(
+            err.for_day(1).to_string(),
+            r#"day 1, line 3, column 3: invalid digit found in string in "x3""#
+        );
+    }
+
+    #[test]
+    fn test_parse_error_location_in_block() {
+        let input = "1\n2\n\n3\nfour";
+        let err = Blocks::<Parsing<u32>>::try_from_input(input).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 1));
+        assert_eq!(err.text, "four");
+    }
+
+    #[test]
+    fn test_parse_error_location_after_normalization() {
+        let err = sonar::Solver::try_run_on("\n\n1\n2\nx3").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 1));
+        let err = sonar::Solver::try_run_on("\n    1\n     2\n\n    3x  \n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (5, 5, "3x"));
+        let err = sonar::Solver::try_run_on("\u{feff}1\r\n  \r\nx\r\n").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+
+    #[test]
+    fn test_borrowed_lines() {
+        let input = String::from("[<>]\n  {()}\n");
+        let lines = Lines::try_from_input(&input).unwrap();
+        assert_eq!(lines, ["[<>]", "{()}"]);
+        assert_eq!(offset_of(&input, lines[1]), 7);
+    }
+
+    #[test]
+    fn test_separated_fields() {
+        assert_eq!(
+            Csv::<i32>::try_from_input("3,-4, 3\n1,2"),
+            Ok(vec![3, -4, 3, 1, 2])
+        );
+        assert_eq!(
+            Separated::<u8, ' '>::try_from_input("1 2 3"),
+            Ok(vec![1, 2, 3])
+        );
+
+        let err = Csv::<u32>::try_from_input("16,1,2,O,4").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 8));
+        assert_eq!(err.text, "O");
+        assert!(Csv::<u32>::try_from_input("1,,2").is_err());
+    }
+
+    #[test]
+    fn test_grids() {
+        let grid = Grid::<u8>::try_from_input("219\n398\n985\n876").unwrap();
+        assert_eq!((grid.height(), grid.width()), (4, 3));
+        assert_eq!(grid[(1, 2)], 8);
+        assert_eq!(grid[5], 8);
+        assert_eq!(grid.position(5), (1, 2));
+        assert_eq!(grid.get(1, 3), None);
+        assert_eq!(grid.rows().last(), Some(&[8, 7, 6][..]));
+
+        let map = Grid::<bool>::try_from_input("#.\n.#").unwrap();
+        assert_eq!(map.cells(), [true, false, false, true]);
+
+        let err = Grid::<u8>::try_from_input("219\n39\n985").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+        assert_eq!(err.message, "expected a row of width 3, got 2");
+        let err = Grid::<u8>::try_from_input("219\n3x8").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+
+        let input = String::from(">.v\n.v>\n");
+        let map = ByteGrid::try_from_input(&input).unwrap();
+        assert_eq!((map.height(), map.width()), (2, 3));
+        assert_eq!(
+            (map[(0, 2)], map.get(1, 2), map.get(2, 0)),
+            (b'v', Some(b'>'), None)
+        );
+        assert_eq!(map.rows().collect::<Vec<_>>(), [b">.v", b".v>"]);
+        assert_eq!(map.row(1).as_ptr(), input[4..].as_ptr());
+        let err = ByteGrid::try_from_input(">.v\n.v").unwrap_err();
+        assert_eq!(err.message, "expected a row of width 3, got 2");
+        assert_eq!(ByteGrid::try_from_input("").unwrap().height(), 0);
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "7,4,9\n\n1 2\n3 4\n\n5 6\n7 8";
+        let (draws, boards) =
+            Header::<Csv<u8>, Blocks<Separated<u8, ' '>>>::try_from_input(input).unwrap();
+        assert_eq!(draws, [7, 4, 9]);
+        assert_eq!(boards, [[1, 2, 3, 4], [5, 6, 7, 8]]);
+
+        let (template, rules, rest) =
+            Sections::<(As<String>, Parsing<u8>, As<String>)>::try_from_input("NN\n\n1\n2\n\nx")
+                .unwrap();
+        assert_eq!(
+            (template, rules, rest),
+            (vec!["NN".into()], vec![1, 2], vec!["x".into()])
+        );
+
+        let err =
+            Header::<Csv<u8>, Blocks<Separated<u8, ' '>>>::try_from_input("7,4\n\n1 2\n\n3 x")
+                .unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (5, 3, "x"));
+        let err = Header::<Csv<u8>, Csv<u8>>::try_from_input("7,4").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Cuboid {
+        on: bool,
+        x: (i32, i32),
+        z: Option<i32>,
+    }
+
+    impl FromCaptures for Cuboid {
+        const PATTERN: &'static str =
+            r"(?P<state>on|off) x=(?P<x0>\S+)\.\.(?P<x1>\S+?)(, z=(?P<z>\S+))?";
+
+        fn from_captures(captures: &Fields<'_>) -> Result<Self, ParseError> {
+            Ok(Self {
+                on: captures.str("state") == Some("on"),
+                x: (captures.parse("x0")?, captures.parse("x1")?),
+                z: captures.str("z").map(|_| captures.parse("z")).transpose()?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_matching_lines() {
+        let cuboids = Matching::<Cuboid>::try_from_input("on x=-5..7\noff x=1..2, z=3").unwrap();
+        assert_eq!(
+            cuboids,
+            [
+                Cuboid {
+                    on: true,
+                    x: (-5, 7),
+                    z: None
+                },
+                Cuboid {
+                    on: false,
+                    x: (1, 2),
+                    z: Some(3)
+                },
+            ]
+        );
+
+        let err = Matching::<Cuboid>::try_from_input("on x=1..2\nup x=1..2").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.text.as_str()),
+            (2, 1, "up x=1..2")
+        );
+        assert!(err.message.starts_with("expected a line matching"));
+
+        let err = Matching::<Cuboid>::try_from_input("on x=1..2\non x=1..two").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 9, "two"));
+        assert_eq!(err.message, "invalid x1: invalid digit found in string");
+    }
+
+    // The solvers that the tests below share, both registered as day 1.
+    #[allow(unused_mut)]
+    mod sonar {
+        register!(
+            1, "Sonar Sweep", "input/day1.txt";
+            (input: chunk parse u64) -> usize, String {
+                input.len();
+                input.iter().flatten().max().map(u64::to_string).unwrap_or_default()
+            }
+            examples: [
+                (
+                    r#"
+                    1
+                    3
+
+                    2
+                    "#,
+                    2,
+                    "3",
+                ),
+            ];
+        );
+    }
+
+    // Pending, as no tests are generated for pending days and its example is wrong on purpose.
+    #[allow(unused_mut)]
+    mod runner {
+        use super::*;
+
+        register!(
+            1, "Sonar Sweep", "input/day1.txt";
+            pending run(input: verbatim Blocks<Raw<Parsing<u64>>>) -> u64 {
+                let numbers = input.iter().flatten();
+                (numbers.clone().sum::<u64>(), numbers.product::<u64>())
+            }
+            examples: [(r#"1"#, 0, 0)];
+            answers: (0, 0);
+        );
+    }
+
+    #[test]
+    fn test_separate_output_types() {
+        let input = "3\n1\n\n2";
+        assert_eq!(sonar::Solver::part1_on(input), 2);
+        assert_eq!(sonar::Solver::part2_on(input), "3");
+
+        let solution = sonar::Solver::solve_on(input, Timing::Once).unwrap();
+        assert_eq!(solution.part1, Answer::Unsigned(2));
+        assert_eq!(solution.part2, Answer::Text(String::from("3")));
+        assert_eq!(solution.spread, None);
+    }
+
+    #[test]
+    fn test_adaptive_timing() {
+        let timing = Timing::Adaptive(Duration::from_millis(2));
+        let solution = sonar::Solver::solve_on("3\n1\n\n2", timing).unwrap();
+        assert_eq!(solution.part1, Answer::Unsigned(2));
+        let (time1, _) = solution.timings.unwrap();
+        let (spread1, spread2) = solution.spread.unwrap();
+        assert!(spread1.low <= time1 && time1 <= spread1.high);
+        assert!(spread1.runs > 1 && spread2.runs > 1);
+
+        let solution = sonar::Solver::solve_part_on(Part::Two, "3\n1\n\n2", timing).unwrap();
+        assert_eq!(solution.answer, Answer::Text(String::from("3")));
+        assert!(solution.spread.is_some());
+
+        assert!(sonar::Solver::adaptive());
+        assert!(!runner::Solver::adaptive());
+        let solution = runner::Solver::solve_on("3\n1\n2", timing).unwrap();
+        assert_eq!(
+            (solution.part1, solution.spread),
+            (Answer::Unsigned(6), None)
+        );
+    }
+
+    #[test]
+    fn test_pending_days() {
+        assert!(!Day::of::<runner::Solver>().implemented);
+        assert_eq!(runner::Solver::run_on(runner::Solver::EXAMPLES[0]), (1, 1));
+    }
+
+    #[test]
+    fn test_input_normalization() {
+        assert!(matches!(normalize("1\n2\n"), Cow::Borrowed("1\n2")));
+        assert_eq!(normalize("\u{feff}1\r\n2\r\n\r\n3\r\n"), "1\n2\n\n3");
+        assert_eq!(
+            normalize("\n        1\n         2 \n\n        3\n        "),
+            "1\n 2\n\n3"
+        );
+        assert_eq!(normalize("a\n  \t\nb"), "a\n\nb");
+
+        let blocks = "1\r\n2\r\n  \r\n3\r\n";
+        assert_eq!(sonar::Solver::part1_on(blocks), 2);
+        let parsed = Blocks::<Parsing<u32>>::try_from_input(&normalize(blocks)).unwrap();
+        assert_eq!(parsed, [vec![1, 2], vec![3]]);
+        assert!(runner::Solver::try_run_on("\u{feff}3\n1").is_err());
+        assert_eq!(runner::Solver::try_run_on("3\n1\n\n2"), Ok((6, 6)));
+    }
+
+    #[test]
+    fn test_answer_normalization() {
+        assert_eq!(Answer::from(3_u8), Answer::from(3_i64));
+        assert_eq!(Answer::from(-3_i32), Answer::Signed(-3));
+        assert_eq!(Answer::from(BigInt::from(u64::MAX)), Answer::from(u64::MAX));
+        let big = BigInt::from(u128::MAX) * 2_u8;
+        assert_eq!(Answer::from(big.clone()), Answer::Big(big));
+        for s in [
+            "42",
+            "-7",
+            "680564733841876926926749214863536422910",
+            "007",
+            "EFJKZLBL",
+        ] {
+            assert_eq!(s.parse::<Answer>().unwrap().to_string(), s);
+        }
+        assert_eq!("007".parse(), Ok(Answer::from("007")));
+    }
+}
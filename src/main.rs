@@ -22,88 +22,411 @@
 #[macro_use]
 extern crate aoc2021;
 
-use aoc2021::{PuzzleSolution, Solution};
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-
-fn main() {
-    for day in std::env::args()
-        .skip(1)
-        .filter_map(|s| s.parse::<u8>().ok())
-    {
-        let PuzzleSolution {
-            part1,
-            part2,
-            timings,
-        } = match day {
-            1 => day01::Solver::solve(),
-            2 => day02::Solver::solve(),
-            3 => day03::Solver::solve(),
-            4 => day04::Solver::solve(),
-            5 => day05::Solver::solve(),
-            6 => day06::Solver::solve(),
-            7 => day07::Solver::solve(),
-            8 => day08::Solver::solve(),
-            9 => day09::Solver::solve(),
-            10 => day10::Solver::solve(),
-            11 => day11::Solver::solve(),
-            12 => day12::Solver::solve(),
-            13 => day13::Solver::solve(),
-            14 => day14::Solver::solve(),
-            15 => day15::Solver::solve(),
-            16 => day16::Solver::solve(),
-            17 => day17::Solver::solve(),
-            18 => day18::Solver::solve(),
-            19 => day19::Solver::solve(),
-            20 => day20::Solver::solve(),
-            21 => day21::Solver::solve(),
-            22 => day22::Solver::solve(),
-            23 => day23::Solver::solve(),
-            24 => day24::Solver::solve(),
-            25 => day25::Solver::solve(),
-            x => unimplemented!("Day {} is not yet implemented", x),
+use answers::{Answers, Expected, Key, Verdict};
+use aoc2021::{Answer, Day, PartSolution, PuzzleSolution, Registry, Timing};
+use args::{Bench, Command, Days, Fetch, New, Readme, Run, Verify};
+use eyre::{bail, WrapErr};
+use output::Report;
+use std::{collections::HashSet, io::Read, time::Instant};
+
+mod answers;
+mod args;
+mod bench;
+mod fetch;
+mod jobs;
+mod output;
+mod readme;
+mod scaffold;
+
+registry!(
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,
+);
+
+fn read_input(path: &str) -> eyre::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin()
+            .read_to_string(&mut input)
+            .wrap_err("Could not read input from stdin")?;
+        Ok(input)
+    } else {
+        std::fs::read_to_string(path).wrap_err_with(|| format!("Could not read input from {path}"))
+    }
+}
+
+fn main() -> eyre::Result<()> {
+    let registry = registry();
+
+    match Command::parse()? {
+        Command::Run(args) => run(&registry, args),
+        Command::Bench(args) => bench(&registry, args),
+        Command::Readme(args) => readme(&registry, args),
+        Command::Fetch(args) => fetch(&registry, args),
+        Command::Verify(args) => verify(&registry, args),
+        Command::New(args) => new(&registry, args),
+        Command::List => {
+            list(&registry);
+            Ok(())
+        }
+    }
+}
+
+/// Resolves a selection of days, defaulting to all implemented days, in the order of the days.
+///
+/// Days that are named explicitly are always selected, while `all`, `latest`, and ranges
+/// only select pending days if `pending` is set.
+fn select_days<'a>(
+    registry: &'a Registry,
+    days: &[Days],
+    pending: bool,
+) -> eyre::Result<Vec<&'a Day>> {
+    let included = |d: &&Day| pending || d.implemented;
+    let get = |day: u8| {
+        registry
+            .get(day)
+            .ok_or_else(|| eyre::eyre!("Day {} is not registered", day))
+    };
+
+    let mut selected = Vec::new();
+    for &days in if days.is_empty() { &[Days::All] } else { days } {
+        match days {
+            Days::All => selected.extend(registry.iter().filter(included)),
+            Days::Latest => selected.extend(registry.iter().rfind(included)),
+            Days::Day(day) => selected.push(get(day)?),
+            Days::Range(from, to) => {
+                for day in from..=to {
+                    selected.extend(Some(get(day)?).filter(included));
+                }
+            }
+        }
+    }
+
+    let mut seen = HashSet::new();
+    selected.retain(|d| seen.insert(d.day));
+    selected.sort_by_key(|d| d.day);
+    Ok(selected)
+}
+
+fn list(registry: &Registry) {
+    let width = registry
+        .iter()
+        .map(|d| d.title.len())
+        .max()
+        .unwrap_or_default();
+    for day in registry.iter() {
+        println!(
+            "Day {:02}  {:width$}  {:7}  {}",
+            day.day,
+            day.title,
+            if day.implemented { "done" } else { "pending" },
+            day.input_path,
+            width = width,
+        );
+    }
+}
+
+/// What `run` computed for a day: both parts, or only the part that was asked for.
+enum Solved {
+    Both(PuzzleSolution<Answer>),
+    Part(PartSolution<Answer>),
+}
+
+fn run(registry: &Registry, args: Run) -> eyre::Result<()> {
+    let Run {
+        days,
+        input,
+        example,
+        part,
+        format,
+        jobs,
+        timeout,
+        timing,
+    } = args;
+    let days = select_days(registry, &days, false)?;
+    if input.is_some() && days.len() != 1 {
+        bail!("--input can only be used with a single day");
+    }
+    if let Some(example) = example {
+        if let Some(day) = days.iter().find(|d| d.examples.len() < example) {
+            bail!("Day {} has no example {}", day.day, example);
+        }
+    }
+    if timing != Timing::Once {
+        if let Some(day) = days.iter().find(|d| !d.adaptive) {
+            bail!("Day {} can't be timed adaptively", day.day);
+        }
+    }
+    let input = input.as_deref().map(read_input).transpose()?;
+
+    let start = Instant::now();
+    let mut report = Report::begin(format);
+    let mut failed = 0_usize;
+    let activity = jobs::Activity::default();
+    jobs::run_ordered(
+        &days,
+        jobs,
+        &activity,
+        |&&day| {
+            let input = input.clone();
+            let solved = jobs::isolated(timeout, &activity, move || {
+                let input = example.map_or_else(
+                    || input.as_deref().unwrap_or_else(|| day.puzzle_input()),
+                    |example| day.examples[example - 1],
+                );
+                part.map_or_else(
+                    || day.solve_on(input, timing).map(Solved::Both),
+                    |part| day.solve_part_on(part, input, timing).map(Solved::Part),
+                )
+            });
+            (day.day, solved)
+        },
+        |(day, solved), contended| match solved {
+            Ok(Ok(Solved::Both(solution))) => report.solution(day, &solution, contended),
+            Ok(Ok(Solved::Part(solution))) => report.part_solution(day, &solution, contended),
+            Ok(Err(e)) => {
+                report.failure(day, &e, contended);
+                failed += 1;
+            }
+            Err(failure) => {
+                report.failure(day, &failure, contended);
+                failed += 1;
+            }
+        },
+    );
+    report.finish((jobs > 1).then(|| start.elapsed()));
+
+    if failed > 0 {
+        bail!("{} day(s) failed", failed);
+    }
+
+    Ok(())
+}
+
+fn bench(registry: &Registry, args: Bench) -> eyre::Result<()> {
+    let Bench {
+        days,
+        warmup,
+        runs,
+        export_markdown,
+    } = args;
+
+    let days = select_days(registry, &days, false)?;
+
+    let mut measurements = Vec::with_capacity(days.len());
+    for day in days {
+        let measurement = bench::measure(day, warmup, runs)?;
+        bench::print(&measurement);
+        measurements.push(measurement);
+    }
+
+    if let Some(path) = export_markdown {
+        std::fs::write(&path, bench::markdown(&measurements))
+            .wrap_err_with(|| format!("Could not write benchmark results to {path}"))?;
+    }
+
+    Ok(())
+}
+
+fn readme(registry: &Registry, args: Readme) -> eyre::Result<()> {
+    let Readme {
+        template,
+        output,
+        warmup,
+        runs,
+    } = args;
+
+    let template = std::fs::read_to_string(&template)
+        .wrap_err_with(|| format!("Could not read the README template from {template}"))?;
+
+    let measurements = registry
+        .iter()
+        .filter(|d| d.implemented)
+        .map(|day| bench::measure(day, warmup, runs))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    std::fs::write(&output, readme::render(&template, registry, &measurements))
+        .wrap_err_with(|| format!("Could not write the README to {output}"))?;
+
+    Ok(())
+}
+
+fn fetch(registry: &Registry, args: Fetch) -> eyre::Result<()> {
+    let Fetch {
+        days,
+        force,
+        base_url,
+        session_file,
+    } = args;
+
+    let days = select_days(registry, &days, true)?;
+
+    let mut cookie = None;
+    let mut failed = 0_usize;
+    for day in days {
+        let session = || -> eyre::Result<String> {
+            if cookie.is_none() {
+                cookie = Some(fetch::read_session(&session_file)?);
+            }
+            Ok(cookie.clone().unwrap_or_default())
+        };
+        match fetch::fetch(
+            day.day,
+            std::path::Path::new(day.input_path),
+            &base_url,
+            session,
+            force,
+        ) {
+            Ok(fetch::Fetched::Written(bytes)) => {
+                println!(
+                    "Day {:02}: wrote {bytes} bytes to {}",
+                    day.day, day.input_path
+                );
+            }
+            Ok(fetch::Fetched::Cached) => {
+                println!(
+                    "Day {:02}: {} already exists, use --force to overwrite",
+                    day.day, day.input_path
+                );
+            }
+            Err(e) => {
+                eprintln!("Day {:02}: {e:#}", day.day);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("Could not fetch {} input(s)", failed);
+    }
+
+    Ok(())
+}
+
+fn verify(registry: &Registry, args: Verify) -> eyre::Result<()> {
+    let Verify {
+        days,
+        input,
+        answers: answers_path,
+        record,
+        hash,
+    } = args;
+    let days = select_days(registry, &days, false)?;
+    if input.is_some() && days.len() != 1 {
+        bail!("--input can only be used with a single day");
+    }
+    let input = input.as_deref().map(read_input).transpose()?;
+    let mut answers = Answers::load(&answers_path)?;
+
+    let mut failed = 0_usize;
+    let mut crashed = 0_usize;
+    let activity = jobs::Activity::default();
+    for &day in days {
+        let owned = input.clone();
+        let solved = jobs::isolated(None, &activity, move || {
+            let input = owned.as_deref().unwrap_or_else(|| day.puzzle_input());
+            day.solve_on(input, Timing::Once)
+        });
+        let solution = match solved {
+            Ok(Ok(solution)) => solution,
+            Ok(Err(e)) => {
+                eprintln!("Day {:02} failed: {e}", day.day);
+                crashed += 1;
+                continue;
+            }
+            Err(failure) => {
+                eprintln!("Day {:02} failed: {failure}", day.day);
+                crashed += 1;
+                continue;
+            }
         };
+        let input = answers::fingerprint(input.as_deref().unwrap_or_else(|| day.puzzle_input()));
 
-        if let Some((time1, time2)) = timings {
-            println!(
-                "Day {:02} Part 1:\t{} (took {})",
-                day,
-                part1,
-                humantime::format_duration(time1)
-            );
-            println!(
-                "Day {:02} Part 2:\t{} (took {})",
-                day,
-                part2,
-                humantime::format_duration(time2)
-            );
-        } else {
-            println!("Day {:02} Part 1:\t{}", day, part1);
-            println!("Day {:02} Part 2:\t{}", day, part2);
+        for (part, answer) in [(1, &solution.part1), (2, &solution.part2)] {
+            let key = Key {
+                day: day.day,
+                part,
+                input: input.clone(),
+            };
+            let verdict = match answers.check(&key, answer) {
+                Verdict::Pass => String::from("pass"),
+                Verdict::Fail(Expected::Hashed(_)) => {
+                    failed += 1;
+                    format!("FAIL (got {answer}, which does not match the hashed answer)")
+                }
+                Verdict::Fail(expected) => {
+                    failed += 1;
+                    format!("FAIL (got {answer}, expected {expected})")
+                }
+                Verdict::Unknown if record => {
+                    let expected = if hash {
+                        Expected::hashed(answer)
+                    } else {
+                        Expected::Plain(answer.clone())
+                    };
+                    answers.record(key, expected);
+                    String::from("unknown (recorded)")
+                }
+                Verdict::Unknown => String::from("unknown"),
+            };
+            println!("Day {:02} Part {part}:\t{verdict}", day.day);
         }
     }
+
+    if record {
+        answers.save(&answers_path)?;
+    }
+    if failed > 0 || crashed > 0 {
+        bail!(
+            "{} answer(s) did not match, and {} day(s) failed",
+            failed,
+            crashed
+        );
+    }
+
+    Ok(())
+}
+
+fn new(registry: &Registry, args: New) -> eyre::Result<()> {
+    let New {
+        day,
+        title,
+        shape,
+        example,
+        force,
+    } = args;
+
+    let title = title
+        .or_else(|| registry.get(day).map(|d| d.title.to_string()))
+        .ok_or_else(|| eyre::eyre!("Day {} needs a --title", day))?;
+    let example = example.then(|| read_input("-")).transpose()?;
+
+    let src = std::path::Path::new("src");
+    let source = scaffold::source(day, &title, shape, example.as_deref());
+    scaffold::write_day(src, day, &source, force)?;
+    scaffold::create_input(src, day)?;
+    scaffold::register(src, day)?;
+
+    println!("Day {day:02}: created src/day{day:02}.rs");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_days() {
+        let registry = registry();
+        let days = |selection: &[Days]| {
+            select_days(&registry, selection, false)
+                .unwrap()
+                .iter()
+                .map(|d| d.day)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(days(&[Days::Day(10), Days::Day(6)]), [6, 10]);
+        assert_eq!(days(&[Days::Day(3), Days::Range(1, 4)]), [1, 2, 3, 4]);
+        assert!(select_days(&registry, &[Days::Day(26)], false).is_err());
+    }
 }
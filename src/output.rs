@@ -0,0 +1,525 @@
+use aoc2021::{Allocations, Answer, Part, PartSolution, PuzzleSolution, Spread};
+use parse_display::{Display, FromStr};
+use std::{
+    fmt::{Display, Write},
+    time::Duration,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, FromStr)]
+#[display(style = "lowercase")]
+pub(crate) enum Format {
+    Text,
+    Json,
+    Csv,
+}
+
+pub(crate) struct Report {
+    format: Format,
+    rows: usize,
+    summary: Summary,
+}
+
+#[derive(Default)]
+struct Summary {
+    days: usize,
+    parse_time: Duration,
+    total_time: Duration,
+    complete: bool,
+    contended: bool,
+}
+
+impl Report {
+    pub(crate) fn begin(format: Format) -> Self {
+        match format {
+            Format::Text => {}
+            Format::Json => print!("{{\"parts\":["),
+            Format::Csv => {
+                println!(concat!(
+                    "day,part,answer,time_ns,parse_ns,total_ns,timed,contended,wall_ns,",
+                    "allocs,alloc_bytes,peak_bytes,parse_allocs,parse_alloc_bytes,parse_peak_bytes,",
+                    "runs,low_ns,high_ns,error"
+                ));
+            }
+        }
+        Self {
+            format,
+            rows: 0,
+            summary: Summary {
+                complete: true,
+                ..Summary::default()
+            },
+        }
+    }
+
+    pub(crate) fn solution(&mut self, day: u8, solution: &PuzzleSolution<Answer>, contended: bool) {
+        let PuzzleSolution {
+            part1,
+            part2,
+            timings,
+            spread,
+            parse_time,
+            allocations,
+            parse_allocations,
+        } = solution;
+        let (time1, time2) = timings.map_or((None, None), |(t1, t2)| (Some(t1), Some(t2)));
+        let (spread1, spread2) = spread.map_or((None, None), |(s1, s2)| (Some(s1), Some(s2)));
+        let (allocations1, allocations2) =
+            allocations.map_or((None, None), |(a1, a2)| (Some(a1), Some(a2)));
+        let day = DayTimings {
+            day,
+            parse_time: *parse_time,
+            total_time: solution.total_time(),
+            contended,
+            parse_allocations: *parse_allocations,
+        };
+        self.day(
+            &day,
+            &[
+                PartRow {
+                    part: Part::One,
+                    answer: part1,
+                    time: time1,
+                    spread: spread1,
+                    allocations: allocations1,
+                },
+                PartRow {
+                    part: Part::Two,
+                    answer: part2,
+                    time: time2,
+                    spread: spread2,
+                    allocations: allocations2,
+                },
+            ],
+        );
+    }
+
+    pub(crate) fn part_solution(
+        &mut self,
+        day: u8,
+        solution: &PartSolution<Answer>,
+        contended: bool,
+    ) {
+        let PartSolution {
+            part,
+            answer,
+            time,
+            spread,
+            parse_time,
+            allocations,
+            parse_allocations,
+        } = solution;
+        let day = DayTimings {
+            day,
+            parse_time: *parse_time,
+            total_time: solution.total_time(),
+            contended,
+            parse_allocations: *parse_allocations,
+        };
+        self.day(
+            &day,
+            &[PartRow {
+                part: *part,
+                answer,
+                time: *time,
+                spread: *spread,
+                allocations: *allocations,
+            }],
+        );
+    }
+
+    fn day(&mut self, day: &DayTimings, parts: &[PartRow<'_>]) {
+        self.summary.days += 1;
+        self.summary.parse_time += day.parse_time.unwrap_or_default();
+        match day.total_time {
+            Some(total_time) => self.summary.total_time += total_time,
+            None => self.summary.complete = false,
+        }
+        self.summary.contended |= day.contended;
+
+        if let (Format::Text, Some(parse_time)) = (self.format, day.parse_time) {
+            println!(
+                "Day {:02} Parse:\t({})",
+                day.day,
+                took(parse_time, None, day.contended, day.parse_allocations)
+            );
+        }
+        for part in parts {
+            self.part(day, part);
+        }
+        if let (Format::Text, Some(total_time)) = (self.format, day.total_time) {
+            println!(
+                "Day {:02} Total:\t({})",
+                day.day,
+                took(total_time, None, day.contended, None)
+            );
+        }
+    }
+
+    fn part(&mut self, day: &DayTimings, row: &PartRow<'_>) {
+        let PartRow {
+            part,
+            answer,
+            time,
+            spread,
+            allocations,
+        } = *row;
+        match self.format {
+            Format::Text => time.map_or_else(
+                || println!("Day {:02} Part {part}:\t{answer}", day.day),
+                |time| {
+                    println!(
+                        "Day {:02} Part {part}:\t{answer} ({})",
+                        day.day,
+                        took(time, spread, day.contended, allocations)
+                    );
+                },
+            ),
+            Format::Json => {
+                print!(
+                    "{}\n  {{\"day\":{},\"part\":{},\"answer\":{},\"time_ns\":{},\"parse_ns\":{},\"total_ns\":{},\"timed\":{},\"contended\":{},{},{},{},\"error\":null}}",
+                    if self.rows == 0 { "" } else { "," },
+                    day.day,
+                    part,
+                    json_answer(answer),
+                    nanos(time, "null"),
+                    nanos(day.parse_time, "null"),
+                    nanos(day.total_time, "null"),
+                    time.is_some(),
+                    day.contended,
+                    json_allocations("", allocations),
+                    json_allocations("parse_", day.parse_allocations),
+                    json_spread(spread)
+                );
+            }
+            Format::Csv => println!(
+                "{},{},{},{},{},{},{},{},,{},{},{},",
+                day.day,
+                part,
+                csv_field(&answer.to_string()),
+                nanos(time, ""),
+                nanos(day.parse_time, ""),
+                nanos(day.total_time, ""),
+                time.is_some(),
+                day.contended,
+                csv_allocations(allocations),
+                csv_allocations(day.parse_allocations),
+                csv_spread(spread)
+            ),
+        }
+        self.rows += 1;
+    }
+
+    /// Reports a day that did not produce a solution, as a row without answers.
+    pub(crate) fn failure(&mut self, day: u8, error: &dyn Display, contended: bool) {
+        eprintln!("Day {day:02} failed: {error}");
+        match self.format {
+            Format::Text => {}
+            Format::Json => print!(
+                "{}\n  {{\"day\":{day},\"part\":null,\"answer\":null,\"time_ns\":null,\"parse_ns\":null,\"total_ns\":null,\"timed\":false,\"contended\":{contended},{},{},{},\"error\":{}}}",
+                if self.rows == 0 { "" } else { "," },
+                json_allocations("", None),
+                json_allocations("parse_", None),
+                json_spread(None),
+                json_string(&error.to_string())
+            ),
+            Format::Csv => println!(
+                "{day},,,,,,false,{contended},,,,,,,,,,,{}",
+                csv_field(&error.to_string())
+            ),
+        }
+        self.rows += 1;
+    }
+
+    pub(crate) fn finish(self, wall_time: Option<Duration>) {
+        let Summary {
+            days,
+            parse_time,
+            total_time,
+            complete,
+            contended,
+        } = self.summary;
+
+        match self.format {
+            Format::Text if days > 0 => {
+                let mut notes = String::new();
+                if !complete {
+                    notes.push_str(", not all parts were timed");
+                }
+                if contended {
+                    notes.push_str(", some days ran concurrently");
+                }
+                if let Some(wall_time) = wall_time {
+                    let _ = write!(
+                        notes,
+                        ", {} wall time",
+                        humantime::format_duration(wall_time)
+                    );
+                }
+                println!(
+                    "Total ({} day{}):\t(took {}{})",
+                    days,
+                    if days == 1 { "" } else { "s" },
+                    humantime::format_duration(total_time),
+                    notes
+                );
+            }
+            Format::Json => {
+                let summary = if days > 0 {
+                    format!(
+                        "{{\"days\":{},\"parse_ns\":{},\"total_ns\":{},\"timed\":{},\"contended\":{},\"wall_ns\":{}}}",
+                        days,
+                        parse_time.as_nanos(),
+                        total_time.as_nanos(),
+                        complete,
+                        contended,
+                        nanos(wall_time, "null")
+                    )
+                } else {
+                    String::from("null")
+                };
+                let end = if self.rows == 0 { "" } else { "\n" };
+                println!("{end}],\n\"summary\":{summary}}}");
+            }
+            Format::Csv if days > 0 => println!(
+                ",,,,{},{},{},{},{},,,,,,,,,,",
+                parse_time.as_nanos(),
+                total_time.as_nanos(),
+                complete,
+                contended,
+                nanos(wall_time, "")
+            ),
+            Format::Text | Format::Csv => {}
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Align {
+    Left,
+    Right,
+}
+
+pub(crate) fn markdown_table(header: &[(String, Align)], rows: &[Vec<String>]) -> String {
+    let mut widths = header
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .collect::<Vec<_>>();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    let mut line = |cells: &mut dyn Iterator<Item = (String, Align, usize)>| {
+        for (cell, align, width) in cells {
+            let _ = match align {
+                Align::Left => write!(table, "| {cell:<width$} "),
+                Align::Right => write!(table, "| {cell:>width$} "),
+            };
+        }
+        table.push_str("|\n");
+    };
+
+    line(
+        &mut header
+            .iter()
+            .zip(&widths)
+            .map(|((name, _), &width)| (name.clone(), Align::Left, width)),
+    );
+    line(
+        &mut header.iter().zip(&widths).map(|(&(_, align), &width)| {
+            let rule = match align {
+                Align::Left => format!(":{}", "-".repeat(width - 1)),
+                Align::Right => format!("{}:", "-".repeat(width - 1)),
+            };
+            (rule, Align::Left, width)
+        }),
+    );
+    for row in rows {
+        line(
+            &mut row
+                .iter()
+                .zip(header)
+                .zip(&widths)
+                .map(|((cell, &(_, align)), &width)| (cell.clone(), align, width)),
+        );
+    }
+
+    table
+}
+
+struct DayTimings {
+    day: u8,
+    parse_time: Option<Duration>,
+    total_time: Option<Duration>,
+    contended: bool,
+    parse_allocations: Option<Allocations>,
+}
+
+struct PartRow<'a> {
+    part: Part,
+    answer: &'a Answer,
+    time: Option<Duration>,
+    spread: Option<Spread>,
+    allocations: Option<Allocations>,
+}
+
+fn took(
+    time: Duration,
+    spread: Option<Spread>,
+    contended: bool,
+    allocations: Option<Allocations>,
+) -> String {
+    let mut took = format!("took {}", humantime::format_duration(time));
+    if let Some(Spread { low, high, runs }) = spread {
+        let _ = write!(
+            took,
+            " ± {} per run over {} run{}",
+            humantime::format_duration(high.saturating_sub(low) / 2),
+            runs,
+            if runs == 1 { "" } else { "s" }
+        );
+    }
+    if contended {
+        took.push_str(", contended");
+    }
+    if let Some(Allocations { count, bytes, peak }) = allocations {
+        let _ = write!(
+            took,
+            ", {} allocation{} of {}, {} peak",
+            count,
+            if count == 1 { "" } else { "s" },
+            format_bytes(bytes),
+            format_bytes(peak)
+        );
+    }
+    took
+}
+
+// Sizes are only rounded for display, so precision loss doesn't matter.
+#[allow(clippy::cast_precision_loss)]
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+fn json_allocations(prefix: &str, allocations: Option<Allocations>) -> String {
+    let field = |f: fn(&Allocations) -> usize| {
+        allocations.map_or_else(|| String::from("null"), |a| f(&a).to_string())
+    };
+    format!(
+        "\"{0}allocs\":{1},\"{0}alloc_bytes\":{2},\"{0}peak_bytes\":{3}",
+        prefix,
+        field(|a| a.count),
+        field(|a| a.bytes),
+        field(|a| a.peak)
+    )
+}
+
+fn csv_allocations(allocations: Option<Allocations>) -> String {
+    allocations.map_or_else(
+        || String::from(",,"),
+        |a| format!("{},{},{}", a.count, a.bytes, a.peak),
+    )
+}
+
+fn json_spread(spread: Option<Spread>) -> String {
+    let field = |f: fn(&Spread) -> String| spread.map_or_else(|| String::from("null"), |s| f(&s));
+    format!(
+        "\"runs\":{},\"low_ns\":{},\"high_ns\":{}",
+        field(|s| s.runs.to_string()),
+        field(|s| s.low.as_nanos().to_string()),
+        field(|s| s.high.as_nanos().to_string())
+    )
+}
+
+fn csv_spread(spread: Option<Spread>) -> String {
+    spread.map_or_else(
+        || String::from(",,"),
+        |s| format!("{},{},{}", s.runs, s.low.as_nanos(), s.high.as_nanos()),
+    )
+}
+
+fn nanos(time: Option<Duration>, missing: &str) -> String {
+    time.map_or_else(|| missing.to_string(), |t| t.as_nanos().to_string())
+}
+
+/// Integers beyond 2^53 are quoted, as most JSON readers would round them to a double.
+fn json_answer(answer: &Answer) -> String {
+    const MAX_SAFE: u128 = (1 << 53) - 1;
+    match answer {
+        Answer::Unsigned(n) if *n <= MAX_SAFE => n.to_string(),
+        Answer::Signed(n) if n.unsigned_abs() <= MAX_SAFE => n.to_string(),
+        answer => json_string(&answer.to_string()),
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", u32::from(c));
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escaping() {
+        assert_eq!(json_string("a\"b\\c\n"), r#""a\"b\\c\n""#);
+        assert_eq!(csv_field("1,2"), r#""1,2""#);
+        assert_eq!(csv_field(r#"say "hi""#), r#""say ""hi""""#);
+        assert_eq!(csv_field("42"), "42");
+    }
+
+    #[test]
+    fn test_json_answer() {
+        assert_eq!(
+            json_answer(&Answer::from(9_007_199_254_740_991_u64)),
+            "9007199254740991"
+        );
+        assert_eq!(
+            json_answer(&Answer::from(9_007_199_254_740_992_u64)),
+            "\"9007199254740992\""
+        );
+        assert_eq!(json_answer(&Answer::from(-42)), "-42");
+        assert_eq!(
+            json_answer(&Answer::from(i64::MIN)),
+            "\"-9223372036854775808\""
+        );
+        assert_eq!(json_answer(&Answer::from("ABC")), "\"ABC\"");
+    }
+}
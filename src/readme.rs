@@ -0,0 +1,49 @@
+use crate::{
+    bench::{self, Measurement},
+    output::{markdown_table, Align},
+};
+use aoc2021::Registry;
+
+pub(crate) const DAYS: &str = "{{days}}";
+pub(crate) const RUNTIMES: &str = "{{runtimes}}";
+
+/// Expands the placeholders in the README template.
+pub(crate) fn render(template: &str, registry: &Registry, measurements: &[Measurement]) -> String {
+    template
+        .replace(DAYS, days(registry).trim_end())
+        .replace(RUNTIMES, bench::markdown(measurements).trim_end())
+}
+
+fn days(registry: &Registry) -> String {
+    let header = [
+        (String::from("Day"), Align::Right),
+        (String::from("Title"), Align::Left),
+        (String::from("Stars"), Align::Left),
+    ];
+    let rows = registry
+        .iter()
+        .map(|day| {
+            vec![
+                day.day.to_string(),
+                day.title.to_string(),
+                String::from(if day.implemented { "★★" } else { "" }),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    markdown_table(&header, &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let template = "# AoC\n\n{{days}}\n\n## Runtimes\n\n{{runtimes}}\n";
+        let readme = render(template, &crate::registry(), &[]);
+        assert!(readme.starts_with("# AoC\n\n| Day | Title "));
+        assert!(readme.contains("\n|   1 | Sonar Sweep "));
+        assert!(readme.ends_with("|\n\n## Runtimes\n\n\n"));
+    }
+}
@@ -0,0 +1,249 @@
+use eyre::{bail, eyre, WrapErr};
+use parse_display::{Display, FromStr};
+use std::{fmt::Write, path::Path};
+
+/// The shape of the puzzle input, which decides how `register!` parses it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Display, FromStr)]
+#[display(style = "lowercase")]
+pub(crate) enum Shape {
+    Parse,
+    Chunk,
+    Verbatim,
+    Grid,
+    Bytes,
+    Csv,
+}
+
+struct Template {
+    imports: &'static str,
+    input: &'static str,
+    items: &'static str,
+    parser: &'static str,
+}
+
+impl Shape {
+    fn template(self) -> Template {
+        match self {
+            Self::Parse => Template {
+                imports: "",
+                input: "parse u64",
+                items: "items: &[u64]",
+                parser: "",
+            },
+            Self::Chunk => Template {
+                imports: "",
+                input: "chunk lines",
+                items: "items: &[Vec<&str>]",
+                parser: "",
+            },
+            Self::Verbatim => Template {
+                imports: "use aoc2021::{lines, ParseError, PuzzleInput};\n\n",
+                input: "verbatim Input",
+                items: "items: &[&str]",
+                parser: VERBATIM_PARSER,
+            },
+            Self::Grid => Template {
+                imports: "use aoc2021::Grid;\n\n",
+                input: "grid u8",
+                items: "grid: &Grid<u8>",
+                parser: "",
+            },
+            Self::Bytes => Template {
+                imports: "use aoc2021::ByteGrid;\n\n",
+                input: "bytes",
+                items: "grid: &ByteGrid<'_>",
+                parser: "",
+            },
+            Self::Csv => Template {
+                imports: "",
+                input: "csv i64",
+                items: "items: &[i64]",
+                parser: "",
+            },
+        }
+    }
+}
+
+const VERBATIM_PARSER: &str = r"
+pub struct Input;
+
+impl PuzzleInput for Input {
+    type Out<'a> = Vec<&'a str>;
+
+    fn try_from_input(input: &str) -> Result<Self::Out<'_>, ParseError> {
+        Ok(lines(input).collect())
+    }
+}
+";
+
+/// Renders the source of a new, pending day.
+pub(crate) fn source(day: u8, title: &str, shape: Shape, example: Option<&str>) -> String {
+    let Template {
+        imports,
+        input,
+        items,
+        parser,
+    } = shape.template();
+    let example = example_tuple(example.unwrap_or_default());
+
+    let mut source = String::new();
+    let _ = write!(
+        source,
+        r#"{imports}register!(
+    {day}, {title:?}, "input/day{day}.txt";
+    pending (input: {input}) -> usize {{
+        part1(&input);
+        part2(&input);
+    }}
+    examples: [
+        {example},
+    ];
+    answers: (0, 0);
+);
+
+fn part1({items}) -> usize {{
+    0
+}}
+
+fn part2({items}) -> usize {{
+    0
+}}
+{parser}"#
+    );
+    source
+}
+
+/// Renders an example for `register!`, with answers to be filled in, laid out like the
+/// examples of the other days.
+fn example_tuple(example: &str) -> String {
+    let example = example.trim_matches('\n').trim_end();
+    let mut hashes = String::from("#");
+    while example.contains(&format!("\"{hashes}")) {
+        hashes.push('#');
+    }
+
+    if !example.contains('\n') {
+        return format!("(r{0}\"{1}\"{0}, 0, 0)", hashes, example.trim());
+    }
+
+    let indent = example
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or_default();
+    let mut tuple = format!("(\n            r{hashes}\"\n");
+    for line in example.lines() {
+        if !line.trim().is_empty() {
+            tuple.push_str("            ");
+            tuple.push_str(line[indent..].trim_end());
+        }
+        tuple.push('\n');
+    }
+    let _ = write!(
+        tuple,
+        "            \"{hashes},\n            0,\n            0,\n        )"
+    );
+    tuple
+}
+
+/// A day is only replaced if it is still a pending stub, unless `force` is given.
+pub(crate) fn write_day(src: &Path, day: u8, source: &str, force: bool) -> eyre::Result<()> {
+    let path = src.join(format!("day{day:02}.rs"));
+    if let Ok(existing) = std::fs::read_to_string(&path) {
+        if !force && !existing.contains("\n    pending ") {
+            bail!(
+                "{} already exists and is implemented, use --force to overwrite",
+                path.display()
+            );
+        }
+    }
+    std::fs::write(&path, source).wrap_err_with(|| format!("Could not write {}", path.display()))
+}
+
+pub(crate) fn create_input(src: &Path, day: u8) -> eyre::Result<()> {
+    let path = src.join("input").join(format!("day{day}.txt"));
+    if !path.exists() {
+        std::fs::write(&path, "")
+            .wrap_err_with(|| format!("Could not create {}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Adds the module of a day to the `registry!` invocation in `main.rs`.
+pub(crate) fn register(src: &Path, day: u8) -> eyre::Result<()> {
+    let path = src.join("main.rs");
+    let main = std::fs::read_to_string(&path)
+        .wrap_err_with(|| format!("Could not read {}", path.display()))?;
+    let main = add_module(&main, &format!("day{day:02}"))?;
+    std::fs::write(&path, main).wrap_err_with(|| format!("Could not write {}", path.display()))
+}
+
+fn add_module(main: &str, module: &str) -> eyre::Result<String> {
+    const START: &str = "registry!(\n";
+    let start = main
+        .find(START)
+        .ok_or_else(|| eyre!("Could not find the registry! invocation"))?
+        + START.len();
+    let end = start
+        + main[start..]
+            .find(");")
+            .ok_or_else(|| eyre!("Could not find the end of the registry! invocation"))?;
+
+    let mut modules = main[start..end]
+        .split(',')
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .collect::<Vec<_>>();
+    if !modules.contains(&module) {
+        modules.push(module);
+    }
+    modules.sort_unstable();
+
+    let mut list = String::new();
+    let mut line = String::from("   ");
+    for module in modules {
+        if line.len() + module.len() + 2 > 100 {
+            list.push_str(&line);
+            list.push('\n');
+            line = String::from("   ");
+        }
+        line.push(' ');
+        line.push_str(module);
+        line.push(',');
+    }
+    list.push_str(&line);
+    list.push('\n');
+
+    Ok(format!("{}{list}{}", &main[..start], &main[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_module() {
+        let main = "use x;\n\nregistry!(\n    day01, day02, day04,\n);\n\nfn main() {}\n";
+        assert_eq!(
+            add_module(main, "day03").unwrap(),
+            "use x;\n\nregistry!(\n    day01, day02, day03, day04,\n);\n\nfn main() {}\n"
+        );
+        assert_eq!(add_module(main, "day02").unwrap(), main);
+    }
+
+    #[test]
+    fn test_source() {
+        let source = source(11, "Dumbo Octopus", Shape::Grid, Some("12\n34\n"));
+        assert!(source.starts_with("use aoc2021::Grid;\n\nregister!(\n    11, \"Dumbo Octopus\", \"input/day11.txt\";\n    pending (input: grid u8) -> usize {\n"));
+        assert!(source.contains("fn part1(grid: &Grid<u8>) -> usize {\n"));
+        assert!(source.contains(
+            "    examples: [\n        (\n            r#\"\n            12\n            34\n            \"#,\n            0,\n            0,\n        ),\n    ];\n"
+        ));
+
+        let stub = super::source(11, "Dumbo Octopus", Shape::Parse, None);
+        assert!(stub.contains(
+            "    examples: [\n        (r#\"\"#, 0, 0),\n    ];\n    answers: (0, 0);\n);\n"
+        ));
+    }
+}
@@ -0,0 +1,205 @@
+use std::{
+    hint::black_box,
+    marker::PhantomData,
+    time::{Duration, Instant},
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Timing {
+    #[default]
+    Once,
+    Adaptive(Duration),
+}
+
+impl Timing {
+    pub fn repeat<I, O>(self, input: &I, part: impl FnMut(I) -> O) -> Option<(Duration, Spread)>
+    where
+        I: Clone,
+    {
+        match self {
+            Self::Once => None,
+            Self::Adaptive(min_time) => Some(repeat(min_time, input, part)),
+        }
+    }
+}
+
+// Calling `repeat` on a `&Repeat<I>` with both traits in scope resolves to `Cloned` if `I` can be
+// cloned, and to `Uncloned` otherwise, which only ever times once. That way only the inputs of
+// days that are timed adaptively need to be `Clone`.
+pub struct Repeat<I>(PhantomData<I>);
+
+impl<I> Repeat<I> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<I> Default for Repeat<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub trait Cloned<I> {
+    fn adaptive(&self) -> bool;
+
+    fn repeat<O>(
+        &self,
+        timing: Timing,
+        input: &I,
+        part: impl FnMut(I) -> O,
+    ) -> Option<(Duration, Spread)>;
+}
+
+impl<I: Clone> Cloned<I> for Repeat<I> {
+    fn adaptive(&self) -> bool {
+        true
+    }
+
+    fn repeat<O>(
+        &self,
+        timing: Timing,
+        input: &I,
+        part: impl FnMut(I) -> O,
+    ) -> Option<(Duration, Spread)> {
+        timing.repeat(input, part)
+    }
+}
+
+pub trait Uncloned<I> {
+    fn adaptive(&self) -> bool;
+
+    fn repeat<O>(
+        &self,
+        timing: Timing,
+        input: &I,
+        part: impl FnMut(I) -> O,
+    ) -> Option<(Duration, Spread)>;
+}
+
+impl<I> Uncloned<I> for &Repeat<I> {
+    fn adaptive(&self) -> bool {
+        false
+    }
+
+    fn repeat<O>(&self, _: Timing, _: &I, _: impl FnMut(I) -> O) -> Option<(Duration, Spread)> {
+        None
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Spread {
+    pub low: Duration,
+    pub high: Duration,
+    pub runs: usize,
+}
+
+const MIN_BATCH_TIME: Duration = Duration::from_micros(100);
+
+const STABLE: f64 = 0.01;
+
+const MAX_TIME_FACTOR: u32 = 10;
+
+pub fn repeat<I, O>(
+    min_time: Duration,
+    input: &I,
+    mut part: impl FnMut(I) -> O,
+) -> (Duration, Spread)
+where
+    I: Clone,
+{
+    let mut run_batch = |batch: usize| {
+        let mut inputs = vec![input.clone(); batch].into_iter();
+        let mut outputs = Vec::with_capacity(batch);
+        let start = Instant::now();
+        for input in &mut inputs {
+            outputs.push(black_box(part(black_box(input))));
+        }
+        let elapsed = start.elapsed();
+        drop((inputs, outputs));
+        elapsed
+    };
+
+    let start = Instant::now();
+    let mut batch = 1;
+    while run_batch(batch) < MIN_BATCH_TIME {
+        batch *= 2;
+    }
+
+    let mut samples = Vec::new();
+    loop {
+        let time = run_batch(batch);
+        samples.push(time.as_secs_f64() / f64::from(u32::try_from(batch).unwrap_or(u32::MAX)));
+
+        let elapsed = start.elapsed();
+        let (mean, half_width) = confidence(&samples);
+        let stable = elapsed >= min_time && half_width <= mean * STABLE;
+        if samples.len() > 1 && (stable || elapsed >= min_time * MAX_TIME_FACTOR) {
+            let spread = Spread {
+                low: Duration::from_secs_f64((mean - half_width).max(0.0)),
+                high: Duration::from_secs_f64(mean + half_width),
+                runs: samples.len() * batch,
+            };
+            return (Duration::from_secs_f64(mean), spread);
+        }
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn confidence(samples: &[f64]) -> (f64, f64) {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    if samples.len() < 2 {
+        return (mean, mean);
+    }
+    let variance = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, 1.96 * (variance / n).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeat() {
+        let mut runs = 0;
+        let input = vec![1_u64, 2, 3];
+        let (time, spread) = repeat(Duration::from_millis(5), &input, |mut input| {
+            runs += 1;
+            input.push(4);
+            input.iter().sum::<u64>()
+        });
+        assert!(spread.runs > 1);
+        assert!(runs >= spread.runs);
+        assert!(spread.low <= time && time <= spread.high);
+        assert_eq!(input, [1, 2, 3]);
+
+        let slow = |()| std::thread::sleep(Duration::from_millis(1));
+        let (_, spread) = repeat(Duration::from_nanos(1), &(), slow);
+        assert_eq!(spread.runs, 2);
+    }
+
+    #[test]
+    fn test_repeat_uncloned() {
+        struct Input;
+        let timing = Timing::Adaptive(Duration::from_millis(1));
+        let cloned = &Repeat::<Vec<u8>>::new();
+        assert!(cloned.adaptive());
+        assert!(cloned
+            .repeat(timing, &vec![1], |input| input.len())
+            .is_some());
+        let uncloned = &Repeat::<Input>::new();
+        assert!(!uncloned.adaptive());
+        assert!(uncloned.repeat(timing, &Input, |_| 1).is_none());
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_confidence() {
+        assert_eq!(confidence(&[2.0]), (2.0, 2.0));
+        let (mean, half_width) = confidence(&[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(mean, 2.5);
+        assert!((half_width - 1.96 * 1.290_994 / 2.0).abs() < 1e-6);
+    }
+}